@@ -50,6 +50,7 @@ impl App {
             Controller(controller) => self.on_controller(controller),
             Main(message) => self.main_window.update(&mut self.state, message),
             ThemeChanged(theme) => self.on_theme_changed(theme),
+            ShortcutBound(action, binding) => self.on_shortcut_bound(action, binding),
             SettingsLoaded(settings) => self.on_settings_loaded(settings),
             SettingsSaved => self.on_settings_saved(),
         }
@@ -147,6 +148,9 @@ impl App {
 
     /// Occurs when the global controller is initialized.
     fn on_controller(&mut self, controller: Controller) -> Task<Message> {
+        self.main_window
+            .texture_splitter
+            .set_controller(controller.clone());
         self.state.controller = controller;
         Task::none()
     }
@@ -158,6 +162,17 @@ impl App {
         Task::perform(save_settings(settings), |_| Message::SettingsSaved)
     }
 
+    /// Occurs when a shortcut is rebound.
+    fn on_shortcut_bound(
+        &mut self,
+        action: crate::shortcuts::ShortcutAction,
+        binding: crate::shortcuts::KeyBinding,
+    ) -> Task<Message> {
+        self.state.settings.shortcuts.rebind(action, binding);
+        let settings = self.state.settings.clone();
+        Task::perform(save_settings(settings), |_| Message::SettingsSaved)
+    }
+
     /// Occurs when settings have been loaded.
     fn on_settings_loaded(&mut self, settings: crate::Settings) -> Task<Message> {
         self.state.settings = settings;
@@ -19,6 +19,8 @@ pub struct BakerLayoutConfig<'a, Message: 'a> {
     pub input_slots: Vec<Element<'a, Message>>,
     /// Output preview widget
     pub output_widget: Element<'a, Message>,
+    /// Optional zoom and pixel readout shown beneath the output preview
+    pub inspector: Option<Element<'a, Message>>,
     /// Control widgets (sliders, etc.)
     pub controls: Vec<Element<'a, Message>>,
     /// Action buttons (save, clear, etc.)
@@ -51,19 +53,21 @@ pub fn create_baker_layout<'a, Message: 'a + Clone>(
     // Bottom left: Output preview
     use crate::widget_helpers::control;
 
-    let output_preview = control(
-        text("Output").size(14).into(),
-        column![
-            container(config.output_widget)
-                .width(OUTPUT_SIZE)
-                .height(OUTPUT_SIZE)
-                .center_x(Length::Fill)
-                .center_y(Length::Fill)
-        ]
-        .spacing(6)
-        .align_x(Alignment::Center)
-        .into(),
-    );
+    let mut output_column = column![
+        container(config.output_widget)
+            .width(OUTPUT_SIZE)
+            .height(OUTPUT_SIZE)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+    ]
+    .spacing(6)
+    .align_x(Alignment::Center);
+
+    if let Some(inspector) = config.inspector {
+        output_column = output_column.push(inspector);
+    }
+
+    let output_preview = control(text("Output").size(14).into(), output_column.into());
 
     let output_container = container(output_preview).width(Length::FillPortion(1));
 
@@ -155,11 +159,13 @@ pub fn create_placeholder<'a, Message: 'a>(msg: &'a str, size: f32) -> Element<'
 /// * `value` - Current slider value
 /// * `range` - Min and max values for the slider
 /// * `on_change` - Callback when value changes
+/// * `on_release` - Message sent when the slider is released
 pub fn create_slider_control<'a, Message: 'a + Clone>(
     label: &str,
     value: f64,
     range: std::ops::RangeInclusive<f64>,
     on_change: impl Fn(f64) -> Message + 'a,
+    on_release: Message,
 ) -> Element<'a, Message> {
     use crate::widget_helpers::slider_style;
     use iced::widget::slider;
@@ -167,6 +173,7 @@ pub fn create_slider_control<'a, Message: 'a + Clone>(
     column![
         text(format!("{label}: {value:.2}")).size(13),
         slider(range, value, on_change)
+            .on_release(on_release)
             .step(0.01)
             .width(Length::Fill)
             .style(slider_style)
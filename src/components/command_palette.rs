@@ -0,0 +1,218 @@
+//! Command Palette Module
+//!
+//! A keyboard-driven overlay that fuzzy-searches shaders and shortcut actions.
+//!
+//! Typing is handled from the window's keyboard events rather than a text
+//! input, so the palette works without having to move widget focus. Actions
+//! can also be rebound from here: the next key combination pressed while
+//! capturing becomes the action's binding.
+
+use crate::shortcuts::{ShortcutAction, Shortcuts};
+use crate::windows::MainMessage;
+use crate::{Message, widget_helpers};
+use iced::widget::{Column, button, column, container, row, text};
+use iced::{Alignment, Element, Length};
+
+/// Maximum number of entries shown at once
+const MAX_VISIBLE_ENTRIES: usize = 10;
+
+/// Width of the palette panel
+const PALETTE_WIDTH: f32 = 520.0;
+
+/// Something the palette can run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteEntry {
+    Shader(String),
+    Action(ShortcutAction),
+}
+
+impl PaletteEntry {
+    /// Text that the query is matched against
+    fn label(&self) -> String {
+        match self {
+            PaletteEntry::Shader(name) => format!("Shader: {name}"),
+            PaletteEntry::Action(action) => action.label().to_string(),
+        }
+    }
+}
+
+/// Command palette state
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    /// Action waiting for a new key binding
+    pub capturing: Option<ShortcutAction>,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Create a closed command palette
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the palette with an empty query
+    pub fn show(&mut self) {
+        self.open = true;
+        self.capturing = None;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Close the palette
+    pub fn hide(&mut self) {
+        self.open = false;
+        self.capturing = None;
+    }
+
+    /// Append typed text to the query, skipping control characters
+    pub fn push_text(&mut self, input: &str) {
+        self.query.extend(input.chars().filter(|c| !c.is_control()));
+        self.selected = 0;
+    }
+
+    /// Remove the last character of the query
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    /// Move the highlighted entry, wrapping around the visible list
+    pub fn move_selection(&mut self, delta: isize, shader_names: &[String]) {
+        let count = self.entries(shader_names).len().min(MAX_VISIBLE_ENTRIES);
+        if count == 0 {
+            return;
+        }
+
+        self.selected = (self.selected as isize + delta).rem_euclid(count as isize) as usize;
+    }
+
+    /// The highlighted entry, if any match the query
+    pub fn selected_entry(&self, shader_names: &[String]) -> Option<PaletteEntry> {
+        self.entries(shader_names).into_iter().nth(self.selected)
+    }
+
+    /// Entries matching the query, best match first
+    pub fn entries(&self, shader_names: &[String]) -> Vec<PaletteEntry> {
+        let candidates = shader_names
+            .iter()
+            .map(|name| PaletteEntry::Shader(name.clone()))
+            .chain(
+                ShortcutAction::ALL
+                    .iter()
+                    .filter(|action| **action != ShortcutAction::CommandPalette)
+                    .map(|action| PaletteEntry::Action(*action)),
+            );
+
+        let mut scored: Vec<(i32, PaletteEntry)> = candidates
+            .filter_map(|entry| {
+                fuzzy_score(&self.query, &entry.label()).map(|score| (score, entry))
+            })
+            .collect();
+
+        // Stable sort keeps shaders before actions on equal scores
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Render the palette panel
+    pub fn view<'a>(
+        &'a self,
+        shader_names: &[String],
+        shortcuts: &Shortcuts,
+    ) -> Element<'a, Message> {
+        let prompt = if let Some(action) = self.capturing {
+            text(format!(
+                "Press a key combination for \"{}\" (Esc to cancel)",
+                action.label()
+            ))
+            .size(14)
+        } else if self.query.is_empty() {
+            text("Type to search shaders and actions...").size(14)
+        } else {
+            text(format!("> {}", self.query)).size(14)
+        };
+
+        let mut list = Column::new().spacing(4);
+
+        for (idx, entry) in self
+            .entries(shader_names)
+            .into_iter()
+            .take(MAX_VISIBLE_ENTRIES)
+            .enumerate()
+        {
+            let binding = match &entry {
+                PaletteEntry::Action(action) => shortcuts.describe(*action),
+                PaletteEntry::Shader(_) => String::new(),
+            };
+
+            let style = if idx == self.selected {
+                widget_helpers::primary_button_style
+            } else {
+                widget_helpers::secondary_button_style
+            };
+
+            let mut entry_row = row![
+                button(text(entry.label()).size(13))
+                    .on_press(Message::Main(MainMessage::PaletteEntrySelected(
+                        entry.clone()
+                    )))
+                    .padding([4, 8])
+                    .width(Length::Fill)
+                    .style(style),
+                text(binding).size(12)
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            if let PaletteEntry::Action(action) = entry {
+                entry_row = entry_row.push(
+                    button(text("Rebind").size(12))
+                        .on_press(Message::Main(MainMessage::PaletteRebind(action)))
+                        .padding([4, 8])
+                        .style(widget_helpers::secondary_button_style),
+                );
+            }
+
+            list = list.push(entry_row);
+        }
+
+        container(column![prompt, list].spacing(10))
+            .padding(16)
+            .width(PALETTE_WIDTH)
+            .style(widget_helpers::frame_style)
+            .into()
+    }
+}
+
+/// Score how well a query matches a candidate as a case-insensitive subsequence
+///
+/// Returns None if the characters of `query` don't all appear in order.
+/// Consecutive matches and matches at the start of a word score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = candidate[position..]
+            .iter()
+            .position(|c| *c == query_char)?;
+        let index = position + offset;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
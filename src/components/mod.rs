@@ -1,4 +1,5 @@
 pub mod about;
 pub mod baker_layout;
+pub mod command_palette;
 pub mod droppable_image_slot;
 pub mod texture_converter;
@@ -0,0 +1,111 @@
+//! Alpha Module
+//!
+//! Straight and premultiplied alpha.
+//!
+//! Shaders always work on straight alpha. Inputs marked as premultiplied
+//! are divided by their alpha as they are uploaded, and outputs marked as
+//! premultiplied are multiplied by it just before they are saved, so shader
+//! code never has to know which kind a file holds. Previews can display
+//! their image under either interpretation.
+
+use crate::porter_image::ImageBuffer;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How colour relates to alpha in stored pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlphaMode {
+    /// Colour is stored independently of alpha
+    #[default]
+    Straight,
+    /// Colour is stored already multiplied by alpha
+    Premultiplied,
+}
+
+impl AlphaMode {
+    /// Convert RGBA8 pixels in this mode to straight alpha
+    pub fn to_straight<'a>(&self, rgba: &'a [u8]) -> Cow<'a, [u8]> {
+        match self {
+            AlphaMode::Straight => Cow::Borrowed(rgba),
+            AlphaMode::Premultiplied => {
+                let mut straight = rgba.to_vec();
+                straight
+                    .chunks_exact_mut(4)
+                    .for_each(|pixel| pixel.copy_from_slice(&unpremultiply(pixel)));
+                Cow::Owned(straight)
+            }
+        }
+    }
+
+    /// Convert a straight-alpha buffer to this mode
+    pub fn from_straight(&self, mut buffer: ImageBuffer) -> ImageBuffer {
+        if *self == AlphaMode::Premultiplied {
+            buffer
+                .as_raw_mut()
+                .chunks_exact_mut(4)
+                .for_each(|pixel| pixel.copy_from_slice(&premultiply(pixel)));
+        }
+
+        buffer
+    }
+}
+
+/// How previews interpret the alpha of the image they show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlphaPreview {
+    /// Inputs as their config says, outputs as rendered (straight)
+    #[default]
+    Configured,
+    Straight,
+    Premultiplied,
+}
+
+impl AlphaPreview {
+    /// All interpretations, in picker order
+    pub const ALL: [AlphaPreview; 3] = [
+        AlphaPreview::Configured,
+        AlphaPreview::Straight,
+        AlphaPreview::Premultiplied,
+    ];
+
+    /// Alpha mode to display an image with, given its configured mode
+    pub fn resolve(&self, configured: AlphaMode) -> AlphaMode {
+        match self {
+            AlphaPreview::Configured => configured,
+            AlphaPreview::Straight => AlphaMode::Straight,
+            AlphaPreview::Premultiplied => AlphaMode::Premultiplied,
+        }
+    }
+}
+
+impl std::fmt::Display for AlphaPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            AlphaPreview::Configured => "Alpha as configured",
+            AlphaPreview::Straight => "Straight alpha",
+            AlphaPreview::Premultiplied => "Premultiplied alpha",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Multiply a pixel's colour by its alpha
+pub fn premultiply(pixel: &[u8]) -> [u8; 4] {
+    let alpha = pixel[3] as u32;
+    let scale = |c: u8| ((c as u32 * alpha + 127) / 255) as u8;
+    [scale(pixel[0]), scale(pixel[1]), scale(pixel[2]), pixel[3]]
+}
+
+/// Divide a pixel's colour by its alpha
+///
+/// Fully transparent pixels have no colour to recover and become black.
+pub fn unpremultiply(pixel: &[u8]) -> [u8; 4] {
+    let alpha = pixel[3] as u32;
+    if alpha == 0 {
+        return [0, 0, 0, 0];
+    }
+
+    let scale = |c: u8| ((c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+    [scale(pixel[0]), scale(pixel[1]), scale(pixel[2]), pixel[3]]
+}
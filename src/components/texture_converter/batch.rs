@@ -0,0 +1,444 @@
+//! Batch Module
+//!
+//! Processing whole folders of textures with the selected shader.
+//!
+//! Dropped folders and multi-file drops are expanded into image files, which
+//! are grouped into materials by base name using the same suffix matching as
+//! single drops (`wall_n.png`, `wall_g.png` and `wall_o.png` form `wall`).
+//! Each selected group is then processed on its own with the current
+//! parameters and written as `<base><output suffix>.<extension>`. Folders are
+//! searched on the thread pool, following symlinks at most once per folder
+//! and no deeper than `MAX_FOLDER_DEPTH`. Materials with the same base name
+//! in different folders would write the same files, so a batch with such a
+//! clash is refused until all but one of them are deselected.
+//!
+//! Groups run as a pipeline: while one group renders on the GPU, the next is
+//! decoding and the previous is encoding on the thread pool. Rendering is
+//! serialized, and the number of groups in flight is capped by how much
+//! memory the groups processed so far needed.
+
+use crate::components::texture_converter::frames::{FrameLayout, FramedOutput, render_frames};
+use crate::components::texture_converter::gpu_processor::process_images;
+use crate::components::texture_converter::jobs::{CANCELLED, JobProgress, JobStage};
+use crate::components::texture_converter::parallel;
+use crate::components::texture_converter::save_policy::SavePolicy;
+use crate::components::texture_converter::slot_matching::match_slot;
+use crate::components::texture_converter::types::{ImageFormat, SaveFormats};
+use crate::components::texture_converter::{InputConfig, ShaderConfig};
+use crate::float_images::FloatRange;
+use crate::porter_image::{PorterImage, is_supported_image};
+use futures::lock::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Most groups in flight at once: one decoding, one rendering, one encoding
+const PIPELINE_DEPTH: usize = 3;
+
+/// Decoded image memory the groups in flight may use together
+const MEMORY_BUDGET: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Deepest folder below a dropped folder that is searched for images
+const MAX_FOLDER_DEPTH: usize = 16;
+
+/// Textures that make up one material
+#[derive(Debug, Clone)]
+pub struct MaterialGroup {
+    /// Base file name shared by the textures
+    pub name: String,
+    /// File for each input slot, if found
+    pub files: Vec<Option<PathBuf>>,
+    pub selected: bool,
+}
+
+impl MaterialGroup {
+    /// Descriptions of required inputs with no file
+    pub fn missing_inputs<'a>(&self, inputs: &'a [InputConfig]) -> Vec<&'a str> {
+        inputs
+            .iter()
+            .zip(&self.files)
+            .filter(|(input, file)| input.required && file.is_none())
+            .map(|(input, _)| input.description.as_str())
+            .collect()
+    }
+
+    /// Whether every required input has a file
+    pub fn is_complete(&self, inputs: &[InputConfig]) -> bool {
+        self.missing_inputs(inputs).is_empty()
+    }
+
+    /// File name for one of this material's outputs
+    pub fn output_file_name(&self, suffix: &str, format: ImageFormat) -> String {
+        format!("{}{}.{}", self.name, suffix, format.extension())
+    }
+}
+
+/// Progress of a batch that is being processed
+///
+/// The shader, parameters and formats are captured when the batch starts, so
+/// changing them while it runs doesn't affect the remaining groups.
+#[derive(Debug, Clone)]
+pub struct BatchRun {
+    pub shader: ShaderConfig,
+    pub parameter_values: HashMap<String, f32>,
+    pub formats: SaveFormats,
+    /// Descriptions of outputs unticked for saving
+    pub skipped: HashSet<String>,
+    /// How float inputs outside 0-1 are read
+    pub float_range: FloatRange,
+    /// Resolved before the batch starts, so never `Ask`
+    pub policy: SavePolicy,
+    pub output_folder: PathBuf,
+    /// Job the batch reports through, shared by every group
+    pub progress: JobProgress,
+    /// Held while a group renders, so groups take turns on the GPU
+    pub render_lock: Arc<Mutex<()>>,
+    /// Group indices still waiting to be processed
+    pub pending: Vec<usize>,
+    /// Groups started but not yet finished
+    pub in_flight: usize,
+    /// Most image memory a finished group needed
+    pub peak_group_bytes: u64,
+    pub total: usize,
+    pub completed: usize,
+    pub saved_files: usize,
+    pub failures: Vec<String>,
+}
+
+impl BatchRun {
+    /// Whether another group can start without exceeding the memory budget
+    ///
+    /// Until a group has finished its size is unknown, so the first group runs
+    /// alone.
+    pub fn can_start_group(&self) -> bool {
+        if self.pending.is_empty() {
+            return false;
+        }
+        if self.in_flight == 0 {
+            return true;
+        }
+        if self.peak_group_bytes == 0 {
+            return false;
+        }
+
+        let depth = (MEMORY_BUDGET / self.peak_group_bytes).clamp(1, PIPELINE_DEPTH as u64);
+        (self.in_flight as u64) < depth
+    }
+
+    /// Whether every started group has finished and none are waiting
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty() && self.in_flight == 0
+    }
+}
+
+/// Result of processing one material group
+#[derive(Debug, Clone)]
+pub struct GroupOutput {
+    pub saved_paths: Vec<PathBuf>,
+    /// Memory used by the group's decoded inputs and rendered outputs
+    pub image_bytes: u64,
+}
+
+/// Material groups waiting to be processed
+#[derive(Debug, Clone, Default)]
+pub struct BatchQueue {
+    /// Image files found in the drop, kept so the groups can be rebuilt for
+    /// another shader without searching the folders again
+    files: Vec<PathBuf>,
+    pub groups: Vec<MaterialGroup>,
+    /// Dropped image files that matched no input
+    pub unmatched_files: usize,
+    pub run: Option<BatchRun>,
+}
+
+impl BatchQueue {
+    /// Group image files found by `collect_image_files` for a shader's inputs
+    pub fn from_files(inputs: &[InputConfig], files: Vec<PathBuf>) -> Self {
+        let mut queue = Self {
+            files,
+            ..Default::default()
+        };
+        queue.regroup(inputs);
+        queue
+    }
+
+    /// Rebuild the groups for another shader's inputs
+    pub fn regroup(&mut self, inputs: &[InputConfig]) {
+        (self.groups, self.unmatched_files) = group_files(inputs, &self.files);
+    }
+
+    /// Whether a batch is being processed
+    pub fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Every file the selected groups would write into `folder`
+    ///
+    /// Outputs in `skipped` aren't saved, so they aren't listed.
+    pub fn planned_outputs(
+        &self,
+        shader: &ShaderConfig,
+        formats: &SaveFormats,
+        skipped: &HashSet<String>,
+        folder: &Path,
+    ) -> Vec<PathBuf> {
+        self.runnable_groups(&shader.inputs)
+            .into_iter()
+            .flat_map(|idx| {
+                let group = &self.groups[idx];
+                shader
+                    .outputs
+                    .iter()
+                    .filter(|output| !skipped.contains(&output.description))
+                    .map(move |output| {
+                        let format = formats.for_output(&output.description);
+                        folder.join(group.output_file_name(&output.suffix, format))
+                    })
+            })
+            .collect()
+    }
+
+    /// File names that more than one selected group would write
+    ///
+    /// Groups are keyed by folder as well as base name, but every group
+    /// writes into the same output folder. Names are compared ignoring case,
+    /// as they would clash on case-insensitive file systems.
+    pub fn clashing_outputs(
+        &self,
+        shader: &ShaderConfig,
+        formats: &SaveFormats,
+        skipped: &HashSet<String>,
+    ) -> Vec<PathBuf> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut clashes: Vec<PathBuf> = Vec::new();
+
+        for path in self.planned_outputs(shader, formats, skipped, Path::new("")) {
+            let name = path.to_string_lossy().to_lowercase();
+            if !seen.insert(name) && !clashes.contains(&path) {
+                clashes.push(path);
+            }
+        }
+
+        clashes
+    }
+
+    /// Indices of groups that are selected and can be processed
+    pub fn runnable_groups(&self, inputs: &[InputConfig]) -> Vec<usize> {
+        self.groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| group.selected && group.is_complete(inputs))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// Expand dropped paths into image files
+///
+/// Folders are searched recursively up to `MAX_FOLDER_DEPTH`, and each folder
+/// is read once however many symlinks lead to it, so link cycles end. This
+/// touches the disk for every entry, so call it from the thread pool. Files
+/// are returned in name order.
+pub fn collect_image_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut pending: Vec<(PathBuf, usize)> = paths.iter().map(|path| (path.clone(), 0)).collect();
+
+    while let Some((path, depth)) = pending.pop() {
+        if !path.is_dir() {
+            if is_supported_image(&path) {
+                files.push(path);
+            }
+            continue;
+        }
+
+        let Ok(canonical) = path.canonicalize() else {
+            continue;
+        };
+        if depth > MAX_FOLDER_DEPTH || !visited.insert(canonical) {
+            continue;
+        }
+
+        match std::fs::read_dir(&path) {
+            Ok(entries) => pending
+                .extend(entries.filter_map(|entry| entry.ok().map(|e| (e.path(), depth + 1)))),
+            Err(e) => tracing::warn!("Failed to read folder {}: {}", path.display(), e),
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Group image files into materials by folder and base name
+///
+/// Shaders with a single input take every file as its own material, so a
+/// folder of colour maps can be run through a one-input shader. Returns the
+/// groups and the number of files that matched no input.
+pub fn group_files(inputs: &[InputConfig], files: &[PathBuf]) -> (Vec<MaterialGroup>, usize) {
+    let mut groups: Vec<MaterialGroup> = Vec::new();
+    let mut group_index: HashMap<(PathBuf, String), usize> = HashMap::new();
+    let mut unmatched_files = 0;
+
+    for file in files {
+        let (slot, name) = match match_slot(inputs, file) {
+            Some(found) => (found.slot, found.base),
+            None if inputs.len() == 1 => (0, file_stem(file).to_string()),
+            None => {
+                unmatched_files += 1;
+                continue;
+            }
+        };
+
+        let folder = file.parent().map(Path::to_path_buf).unwrap_or_default();
+        let idx = *group_index
+            .entry((folder, name.to_lowercase()))
+            .or_insert_with(|| {
+                groups.push(MaterialGroup {
+                    name,
+                    files: vec![None; inputs.len()],
+                    selected: true,
+                });
+                groups.len() - 1
+            });
+
+        let slot_file = &mut groups[idx].files[slot];
+        if slot_file.is_none() {
+            *slot_file = Some(file.clone());
+        }
+    }
+
+    // Groups missing required inputs start deselected
+    for group in &mut groups {
+        group.selected = group.is_complete(inputs);
+    }
+
+    (groups, unmatched_files)
+}
+
+/// Process one material group and save its outputs
+///
+/// Outputs in `skipped` are rendered but not saved. Decoding and encoding run
+/// on the thread pool; rendering waits for
+/// `render_lock`, so several groups can be in different stages at once.
+#[allow(clippy::too_many_arguments)]
+pub async fn process_group(
+    group: MaterialGroup,
+    shader: ShaderConfig,
+    parameter_values: HashMap<String, f32>,
+    formats: SaveFormats,
+    skipped: HashSet<String>,
+    float_range: FloatRange,
+    policy: SavePolicy,
+    output_folder: PathBuf,
+    progress: JobProgress,
+    render_lock: Arc<Mutex<()>>,
+) -> Result<GroupOutput, String> {
+    let with_name = |e: String| match e.as_str() {
+        CANCELLED => e,
+        _ => format!("{}: {e}", group.name),
+    };
+
+    progress.stage(JobStage::Decode)?;
+    let files: Vec<PathBuf> = group.files.iter().flatten().cloned().collect();
+    let images = parallel::open_images(files, float_range)
+        .await
+        .map_err(with_name)?;
+    let input_bytes: u64 = images
+        .iter()
+        .map(|image| image_bytes(image.width(), image.height()) * image.frame_count() as u64)
+        .sum();
+
+    // Put the images back at their slot positions
+    let mut opened = images.into_iter().map(Arc::new);
+    let images: Vec<Option<Arc<PorterImage>>> = group
+        .files
+        .iter()
+        .map(|file| file.as_ref().and_then(|_| opened.next()))
+        .collect();
+    let layout = FrameLayout::of(&images);
+
+    // Cubemaps and arrays render every frame
+    let outputs = {
+        let _render = render_lock.lock().await;
+        progress.check()?;
+        if layout.is_multi_frame() {
+            render_frames(
+                images,
+                layout,
+                shader.clone(),
+                parameter_values,
+                &formats.render,
+                &progress,
+            )
+            .await
+            .map_err(with_name)?
+        } else {
+            process_images(
+                images,
+                None,
+                shader.clone(),
+                parameter_values,
+                None,
+                &formats.render,
+                &progress,
+            )
+            .await
+            .map_err(with_name)?
+            .into_iter()
+            .map(|(buffer, description)| {
+                let alpha_mode = shader.output_alpha_mode(&description);
+                (
+                    FramedOutput::single(buffer, layout, alpha_mode),
+                    description,
+                )
+            })
+            .collect()
+        }
+    };
+    let output_bytes: u64 = outputs
+        .iter()
+        .flat_map(|(output, _)| &output.frames)
+        .map(|buffer| image_bytes(buffer.width(), buffer.height()))
+        .sum();
+
+    // Destinations are picked up front so auto-numbering sees a stable folder
+    let mut to_save = Vec::new();
+    for (output, description) in outputs {
+        if skipped.contains(&description) {
+            continue;
+        }
+        let suffix = shader
+            .outputs
+            .iter()
+            .find(|output| output.description == description)
+            .map(|output| output.suffix.as_str())
+            .unwrap_or_default();
+        let format = formats.for_output(&description);
+        let destination = output_folder.join(group.output_file_name(suffix, format));
+        match policy.destination(destination) {
+            Some(path) => to_save.push((output, path)),
+            None => tracing::info!("Skipped existing {}{}", group.name, suffix),
+        }
+    }
+
+    let saved_paths = parallel::save_images(to_save, formats.options, progress).await?;
+
+    Ok(GroupOutput {
+        saved_paths,
+        image_bytes: input_bytes + output_bytes,
+    })
+}
+
+/// Size of an RGBA8 image in memory
+fn image_bytes(width: u32, height: u32) -> u64 {
+    width as u64 * height as u64 * 4
+}
+
+/// File stem as a string, empty if it isn't valid UTF-8
+fn file_stem(path: &Path) -> &str {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+}
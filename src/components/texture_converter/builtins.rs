@@ -0,0 +1,153 @@
+//! Builtins Module
+//!
+//! Values every shader can read without any config entries.
+//!
+//! Each output is rendered with a uniform buffer at `@group(2) @binding(0)`
+//! holding the render target's size and texel size, the size of every input
+//! texture and whether it holds a provided image or the white placeholder,
+//! the index of the output and a random seed. Shaders that don't declare the
+//! binding simply ignore it. Group 1 holds the parameters, and is bound to an
+//! empty group for shaders without any so the built-ins never move.
+//!
+//! The WGSL declaration matching `uniform_data` is:
+//!
+//! ```wgsl
+//! struct BuiltinInput {
+//!     size: vec2<f32>,
+//!     provided: u32,
+//!     _padding: u32,
+//! }
+//!
+//! struct Builtins {
+//!     resolution: vec2<f32>,
+//!     texel_size: vec2<f32>,
+//!     output_index: u32,
+//!     seed: u32,
+//!     input_count: u32,
+//!     _padding: u32,
+//!     inputs: array<BuiltinInput, 8>,
+//! }
+//! ```
+
+use std::hash::{BuildHasher, RandomState};
+use std::sync::LazyLock;
+
+/// Bind group index of the built-ins
+pub const BUILTINS_GROUP: u32 = 2;
+
+/// Inputs the built-ins describe; later inputs are left out
+pub const MAX_BUILTIN_INPUTS: usize = 8;
+
+/// Bytes before the input array
+const HEADER_SIZE: usize = 32;
+
+/// Bytes per input entry
+const INPUT_SIZE: usize = 16;
+
+/// Seed shared by every render in a session, so previews don't shimmer
+static SESSION_SEED: LazyLock<u32> =
+    LazyLock::new(|| RandomState::new().hash_one("builtins seed") as u32);
+
+/// Input textures of one render, as reported to the shader
+#[derive(Debug, Clone)]
+pub struct InputSizes {
+    /// Size of each bound input texture, and whether it holds a provided
+    /// image rather than a placeholder
+    inputs: Vec<((u32, u32), bool)>,
+}
+
+impl InputSizes {
+    /// Sizes of the input textures bound to a render
+    ///
+    /// `provided` says which slots hold images; textures of other slots, and
+    /// any beyond `provided`, are placeholders.
+    pub fn new(textures: &[wgpu::Texture], provided: &[bool]) -> Self {
+        Self {
+            inputs: textures
+                .iter()
+                .enumerate()
+                .map(|(idx, texture)| {
+                    let provided = provided.get(idx).copied().unwrap_or_default();
+                    ((texture.width(), texture.height()), provided)
+                })
+                .collect(),
+        }
+    }
+
+    /// Uniform data for one output, laid out as the WGSL `Builtins` struct
+    fn uniform_data(&self, target: wgpu::Extent3d, output_index: u32) -> Vec<u8> {
+        let (width, height) = (target.width, target.height);
+        let input_count = self.inputs.len().min(MAX_BUILTIN_INPUTS);
+
+        let mut data = Vec::with_capacity(HEADER_SIZE + INPUT_SIZE * MAX_BUILTIN_INPUTS);
+        for value in [
+            width as f32,
+            height as f32,
+            1.0 / width.max(1) as f32,
+            1.0 / height.max(1) as f32,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [output_index, *SESSION_SEED, input_count as u32, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+
+        for &((input_width, input_height), provided) in &self.inputs[..input_count] {
+            data.extend_from_slice(&(input_width as f32).to_le_bytes());
+            data.extend_from_slice(&(input_height as f32).to_le_bytes());
+            data.extend_from_slice(&(provided as u32).to_le_bytes());
+            data.extend_from_slice(&0u32.to_le_bytes());
+        }
+        data.resize(HEADER_SIZE + INPUT_SIZE * MAX_BUILTIN_INPUTS, 0);
+
+        data
+    }
+
+    /// Bind group holding the built-ins for one output rendered at `target`
+    pub fn bind_group(
+        &self,
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        target: wgpu::Extent3d,
+        output_index: u32,
+    ) -> wgpu::BindGroup {
+        let data = self.uniform_data(target, output_index);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Builtins Uniform Buffer"),
+            size: data.len() as u64,
+            usage: wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: true,
+        });
+        buffer
+            .slice(..)
+            .get_mapped_range_mut()
+            .copy_from_slice(&data);
+        buffer.unmap();
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Builtins Bind Group"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        })
+    }
+}
+
+/// Create the bind group layout for the built-ins
+pub fn create_builtins_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Builtins Bind Group Layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    })
+}
@@ -0,0 +1,130 @@
+//! Channels Module
+//!
+//! Channel isolation for the input and output previews.
+//!
+//! Packed textures often carry unrelated data in each channel (e.g. metalness
+//! in alpha), which is invisible once the colour is composited normally. Each
+//! preview can instead show a single channel as greyscale, the colour over a
+//! checkerboard so transparency is visible, or the colour without its alpha
+//! applied. Colour is read as straight or premultiplied depending on the
+//! alpha mode the preview interprets its image with.
+
+use crate::components::texture_converter::alpha::{AlphaMode, unpremultiply};
+use crate::porter_image::ImageBuffer;
+
+/// Size of one checkerboard square in display pixels
+const CHECKER_SIZE: u32 = 8;
+
+/// Light and dark checkerboard shades
+const CHECKER_LIGHT: u8 = 204;
+const CHECKER_DARK: u8 = 153;
+
+/// How a preview displays the channels of its image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelView {
+    /// Colour composited over a checkerboard
+    #[default]
+    Rgb,
+    Red,
+    Green,
+    Blue,
+    Alpha,
+    /// Colour without alpha applied
+    Unpremultiplied,
+}
+
+impl ChannelView {
+    /// All channel views, in toggle order
+    pub const ALL: [ChannelView; 6] = [
+        ChannelView::Rgb,
+        ChannelView::Red,
+        ChannelView::Green,
+        ChannelView::Blue,
+        ChannelView::Alpha,
+        ChannelView::Unpremultiplied,
+    ];
+
+    /// Short label for the toggle button
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChannelView::Rgb => "RGB",
+            ChannelView::Red => "R",
+            ChannelView::Green => "G",
+            ChannelView::Blue => "B",
+            ChannelView::Alpha => "A",
+            ChannelView::Unpremultiplied => "Unpremul",
+        }
+    }
+
+    /// Map a source pixel to the displayed pixel
+    ///
+    /// `alpha` says whether the pixel's colour is already multiplied by its
+    /// alpha. `display_x` and `display_y` position the checkerboard, so it
+    /// stays fixed on screen rather than scaling with the image.
+    pub fn map_pixel(
+        &self,
+        pixel: [u8; 4],
+        alpha: AlphaMode,
+        display_x: u32,
+        display_y: u32,
+    ) -> [u8; 4] {
+        let [r, g, b, a] = pixel;
+
+        match self {
+            ChannelView::Rgb => {
+                let checker = checker_shade(display_x, display_y) as u32;
+                let coverage = a as u32;
+                let blend = |c: u8| match alpha {
+                    AlphaMode::Straight => {
+                        ((c as u32 * coverage + checker * (255 - coverage) + 127) / 255) as u8
+                    }
+                    AlphaMode::Premultiplied => {
+                        (c as u32 + (checker * (255 - coverage) + 127) / 255).min(255) as u8
+                    }
+                };
+                [blend(r), blend(g), blend(b), 255]
+            }
+            ChannelView::Unpremultiplied if alpha == AlphaMode::Premultiplied => {
+                let [r, g, b, _] = unpremultiply(&pixel);
+                [r, g, b, 255]
+            }
+            ChannelView::Red => [r, r, r, 255],
+            ChannelView::Green => [g, g, g, 255],
+            ChannelView::Blue => [b, b, b, 255],
+            ChannelView::Alpha => [a, a, a, 255],
+            ChannelView::Unpremultiplied => [r, g, b, 255],
+        }
+    }
+}
+
+/// Apply a channel view to a whole buffer
+pub fn apply_channel_view(
+    buffer: &ImageBuffer,
+    view: ChannelView,
+    alpha: AlphaMode,
+) -> ImageBuffer {
+    let (width, height) = buffer.dimensions();
+    let mut data = Vec::with_capacity(buffer.as_raw().len());
+
+    for (idx, pixel) in buffer.as_raw().chunks_exact(4).enumerate() {
+        let x = (idx % width as usize) as u32;
+        let y = (idx / width as usize) as u32;
+        data.extend_from_slice(&view.map_pixel(
+            [pixel[0], pixel[1], pixel[2], pixel[3]],
+            alpha,
+            x,
+            y,
+        ));
+    }
+
+    ImageBuffer::from_raw(width, height, data).expect("channel view has matching dimensions")
+}
+
+/// Checkerboard shade at a display position
+fn checker_shade(x: u32, y: u32) -> u8 {
+    if (x / CHECKER_SIZE + y / CHECKER_SIZE) & 1 == 0 {
+        CHECKER_LIGHT
+    } else {
+        CHECKER_DARK
+    }
+}
@@ -0,0 +1,172 @@
+//! Displays Module
+//!
+//! Rendering the preview display handles off the UI thread.
+//!
+//! Zooming, panning and dragging the split divider change what every preview
+//! shows, and rendering a zoomed view touches every display pixel. Refreshes
+//! therefore snapshot the images and settings they need into a
+//! `DisplayRequest`, which renders on the thread pool. Only one request runs
+//! at a time; refreshes asked for meanwhile are merged into the next one, so
+//! a fast drag renders the latest view instead of queueing every step.
+//!
+//! Each rendered display keeps the image it was rendered from, and is only
+//! shown if the preview still holds that image when the render finishes.
+
+use crate::components::texture_converter::alpha::AlphaMode;
+use crate::components::texture_converter::channels::{self, ChannelView};
+use crate::components::texture_converter::compare;
+use crate::components::texture_converter::viewport::{self, ViewTransform};
+use crate::porter_image::ImageBuffer;
+use crate::preview::PreviewPyramid;
+use iced::widget::image::Handle;
+use std::sync::Arc;
+
+/// Which displays a refresh renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DisplayScope {
+    /// Only the compare view, such as while the split divider is dragged
+    Compare,
+    /// Every preview
+    All,
+}
+
+/// An image shown in a preview, with how it is displayed
+#[derive(Debug, Clone)]
+pub struct DisplaySource {
+    pub preview: PreviewPyramid,
+    pub pixels: Arc<ImageBuffer>,
+    pub size: f32,
+    pub channel: ChannelView,
+    pub alpha: AlphaMode,
+}
+
+impl DisplaySource {
+    /// Render this image through the view
+    ///
+    /// Fitted views use the preview pyramid level closest to the size, zoomed
+    /// views are rendered from the full-resolution pixels.
+    fn render(&self, view: &ViewTransform) -> ImageBuffer {
+        if view.is_fitted() {
+            channels::apply_channel_view(
+                self.preview.buffer_for_size(self.size),
+                self.channel,
+                self.alpha,
+            )
+        } else {
+            viewport::render_viewport(&self.pixels, view, self.size, self.channel, self.alpha)
+        }
+    }
+
+    /// Render this image into a display handle
+    fn display(&self, view: &ViewTransform) -> RenderedDisplay {
+        RenderedDisplay {
+            pixels: self.pixels.clone(),
+            handle: crate::core_logic::buffer_to_handle(&self.render(view)),
+        }
+    }
+}
+
+/// What the compare view shows
+#[derive(Debug, Clone)]
+pub enum CompareRequest {
+    /// Input and output composed around the divider
+    Split {
+        input: DisplaySource,
+        output: DisplaySource,
+        position: f32,
+    },
+    /// The input shown next to or instead of the output
+    Input(DisplaySource),
+}
+
+/// Snapshot of everything one refresh renders
+#[derive(Debug, Clone)]
+pub struct DisplayRequest {
+    pub view: ViewTransform,
+    /// Input slots, empty when only the compare view is refreshed
+    pub inputs: Vec<Option<DisplaySource>>,
+    pub output: Option<DisplaySource>,
+    pub compare: Option<CompareRequest>,
+}
+
+impl DisplayRequest {
+    /// Render every requested display
+    pub fn render(&self) -> RenderedDisplays {
+        let view = &self.view;
+
+        let compare = self.compare.as_ref().map(|request| match request {
+            CompareRequest::Split {
+                input,
+                output,
+                position,
+            } => {
+                let before = viewport::render_viewport(
+                    &input.pixels,
+                    view,
+                    input.size,
+                    input.channel,
+                    input.alpha,
+                );
+                let after = viewport::render_viewport(
+                    &output.pixels,
+                    view,
+                    output.size,
+                    output.channel,
+                    output.alpha,
+                );
+                let composed = compare::compose_split(&before, &after, *position);
+                RenderedCompare::Split {
+                    input: input.pixels.clone(),
+                    output: output.pixels.clone(),
+                    handle: crate::core_logic::buffer_to_handle(&composed),
+                }
+            }
+            CompareRequest::Input(input) => RenderedCompare::Input(input.display(view)),
+        });
+
+        RenderedDisplays {
+            inputs: self
+                .inputs
+                .iter()
+                .map(|source| source.as_ref().map(|source| source.display(view)))
+                .collect(),
+            output: self.output.as_ref().map(|source| source.display(view)),
+            compare,
+        }
+    }
+}
+
+/// A display handle with the image it was rendered from
+#[derive(Debug, Clone)]
+pub struct RenderedDisplay {
+    pub pixels: Arc<ImageBuffer>,
+    pub handle: Handle,
+}
+
+impl RenderedDisplay {
+    /// The handle, if it was rendered from `pixels`
+    pub fn handle_for(&self, pixels: Option<&Arc<ImageBuffer>>) -> Option<Handle> {
+        pixels
+            .filter(|pixels| Arc::ptr_eq(pixels, &self.pixels))
+            .map(|_| self.handle.clone())
+    }
+}
+
+/// Rendered compare view, with the images it was rendered from
+#[derive(Debug, Clone)]
+pub enum RenderedCompare {
+    Split {
+        input: Arc<ImageBuffer>,
+        output: Arc<ImageBuffer>,
+        handle: Handle,
+    },
+    Input(RenderedDisplay),
+}
+
+/// Displays rendered by one request
+#[derive(Debug, Clone, Default)]
+pub struct RenderedDisplays {
+    pub inputs: Vec<Option<RenderedDisplay>>,
+    pub output: Option<RenderedDisplay>,
+    pub compare: Option<RenderedCompare>,
+}
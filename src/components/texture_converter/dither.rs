@@ -0,0 +1,257 @@
+//! Dither Module
+//!
+//! Dithered quantisation of rendered outputs to 8 bits per channel.
+//!
+//! Shaders compute in floating point, and rounding smooth gradients straight
+//! to 8 bits leaves visible bands. Outputs with dithering enabled render into
+//! a 16-bit float target instead, and readback adds a threshold from an
+//! ordered (Bayer) or blue-noise pattern to every texel before rounding. The
+//! patterns are indexed by the texel's position in the full image, so tiled
+//! renders line up across tile edges.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Side of the repeating blue-noise tile
+const BLUE_NOISE_SIZE: usize = 64;
+
+/// Spread of the energy filter used to build the blue-noise tile
+const BLUE_NOISE_SIGMA: f32 = 1.5;
+
+/// Threshold order of the 8x8 Bayer matrix
+const BAYER_8X8: [u8; 64] = [
+    0, 32, 8, 40, 2, 34, 10, 42, //
+    48, 16, 56, 24, 50, 18, 58, 26, //
+    12, 44, 4, 36, 14, 46, 6, 38, //
+    60, 28, 52, 20, 62, 30, 54, 22, //
+    3, 35, 11, 43, 1, 33, 9, 41, //
+    51, 19, 59, 27, 49, 17, 57, 25, //
+    15, 47, 7, 39, 13, 45, 5, 37, //
+    63, 31, 55, 23, 61, 29, 53, 21,
+];
+
+/// Blue-noise thresholds, built the first time they are needed
+static BLUE_NOISE: LazyLock<Vec<f32>> = LazyLock::new(build_blue_noise);
+
+/// How an output is quantised to 8 bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DitherMode {
+    /// Round to the nearest value
+    #[default]
+    Off,
+    /// Repeating 8x8 Bayer pattern
+    Ordered,
+    /// Blue noise, which hides the pattern better than Bayer
+    BlueNoise,
+}
+
+impl DitherMode {
+    /// All modes, in picker order
+    pub const ALL: [DitherMode; 3] = [DitherMode::Off, DitherMode::Ordered, DitherMode::BlueNoise];
+
+    /// Format the output is rendered into before it is quantised
+    pub fn render_format(&self) -> wgpu::TextureFormat {
+        match self {
+            DitherMode::Off => wgpu::TextureFormat::Rgba8Unorm,
+            DitherMode::Ordered | DitherMode::BlueNoise => wgpu::TextureFormat::Rgba16Float,
+        }
+    }
+
+    /// Quantise a normalised value at a texel of the full image
+    pub fn quantize(&self, value: f32, x: u32, y: u32) -> u8 {
+        (value.clamp(0.0, 1.0) * 255.0 + 0.5 + self.threshold(x, y))
+            .floor()
+            .clamp(0.0, 255.0) as u8
+    }
+
+    /// Offset added before rounding, between -0.5 and 0.5
+    fn threshold(&self, x: u32, y: u32) -> f32 {
+        match self {
+            DitherMode::Off => 0.0,
+            DitherMode::Ordered => {
+                let rank = BAYER_8X8[(y % 8 * 8 + x % 8) as usize];
+                (rank as f32 + 0.5) / 64.0 - 0.5
+            }
+            DitherMode::BlueNoise => {
+                let (x, y) = (x as usize, y as usize);
+                BLUE_NOISE[y % BLUE_NOISE_SIZE * BLUE_NOISE_SIZE + x % BLUE_NOISE_SIZE]
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DitherMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            DitherMode::Off => "No dither",
+            DitherMode::Ordered => "Ordered",
+            DitherMode::BlueNoise => "Blue noise",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Dithering picked for one output in the UI
+///
+/// An explicit mode is kept even when it matches the default, so changing the
+/// default later doesn't change outputs the user set on their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DitherOverride {
+    /// Follow the mode of outputs without an override
+    #[default]
+    Default,
+    Mode(DitherMode),
+}
+
+impl DitherOverride {
+    /// All choices, in picker order
+    pub const ALL: [DitherOverride; 4] = [
+        DitherOverride::Default,
+        DitherOverride::Mode(DitherMode::Off),
+        DitherOverride::Mode(DitherMode::Ordered),
+        DitherOverride::Mode(DitherMode::BlueNoise),
+    ];
+}
+
+impl std::fmt::Display for DitherOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DitherOverride::Default => write!(f, "Default dither"),
+            DitherOverride::Mode(mode) => mode.fmt(f),
+        }
+    }
+}
+
+/// Dithering of every output
+#[derive(Debug, Clone, Default)]
+pub struct Dithering {
+    /// Mode for outputs without an override
+    pub default: DitherMode,
+    /// Per-output modes keyed by output description
+    pub overrides: HashMap<String, DitherMode>,
+}
+
+impl Dithering {
+    /// Mode an output is quantised with
+    pub fn for_output(&self, description: &str) -> DitherMode {
+        self.overrides
+            .get(description)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// Binary pattern on the blue-noise tile with the filtered energy of its set
+/// texels, both wrapping at the tile edges
+#[derive(Clone)]
+struct NoisePattern {
+    kernel: Vec<f32>,
+    set: Vec<bool>,
+    energy: Vec<f32>,
+}
+
+impl NoisePattern {
+    /// Empty pattern with a Gaussian energy filter
+    fn new() -> Self {
+        let count = BLUE_NOISE_SIZE * BLUE_NOISE_SIZE;
+        let wrap = |d: usize| d.min(BLUE_NOISE_SIZE - d) as f32;
+        let kernel = (0..count)
+            .map(|idx| {
+                let (dx, dy) = (wrap(idx % BLUE_NOISE_SIZE), wrap(idx / BLUE_NOISE_SIZE));
+                (-(dx * dx + dy * dy) / (2.0 * BLUE_NOISE_SIGMA * BLUE_NOISE_SIGMA)).exp()
+            })
+            .collect();
+
+        Self {
+            kernel,
+            set: vec![false; count],
+            energy: vec![0.0; count],
+        }
+    }
+
+    /// Set or clear a texel, updating the energy around it
+    fn toggle(&mut self, idx: usize) {
+        let sign = if self.set[idx] { -1.0 } else { 1.0 };
+        self.set[idx] = !self.set[idx];
+
+        let n = BLUE_NOISE_SIZE;
+        let (px, py) = (idx % n, idx / n);
+        for (other, energy) in self.energy.iter_mut().enumerate() {
+            let dx = (other % n + n - px) % n;
+            let dy = (other / n + n - py) % n;
+            *energy += sign * self.kernel[dy * n + dx];
+        }
+    }
+
+    /// Set texel with the most set texels around it
+    fn tightest_cluster(&self) -> usize {
+        (0..self.set.len())
+            .filter(|&idx| self.set[idx])
+            .max_by(|&a, &b| self.energy[a].total_cmp(&self.energy[b]))
+            .expect("pattern has set texels")
+    }
+
+    /// Clear texel with the fewest set texels around it
+    fn largest_void(&self) -> usize {
+        (0..self.set.len())
+            .filter(|&idx| !self.set[idx])
+            .min_by(|&a, &b| self.energy[a].total_cmp(&self.energy[b]))
+            .expect("pattern has clear texels")
+    }
+}
+
+/// Build the blue-noise thresholds with the void-and-cluster method
+///
+/// A sparse seed pattern is spread out evenly, then every texel is ranked by
+/// when it joins the pattern: seed texels by removing the tightest clusters,
+/// the rest by filling the largest voids. Ranks become thresholds.
+fn build_blue_noise() -> Vec<f32> {
+    let count = BLUE_NOISE_SIZE * BLUE_NOISE_SIZE;
+    let seed_count = count / 10;
+    let mut pattern = NoisePattern::new();
+
+    // Fixed xorshift sequence, so the tile is the same on every run
+    let mut state: u32 = 0x9E37_79B9;
+    let mut placed = 0;
+    while placed < seed_count {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let idx = state as usize % count;
+        if !pattern.set[idx] {
+            pattern.toggle(idx);
+            placed += 1;
+        }
+    }
+
+    // Move the tightest cluster into the largest void until it stays put
+    for _ in 0..count {
+        let cluster = pattern.tightest_cluster();
+        pattern.toggle(cluster);
+        let void = pattern.largest_void();
+        pattern.toggle(void);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0; count];
+
+    let mut removing = pattern.clone();
+    for rank in (0..seed_count).rev() {
+        let idx = removing.tightest_cluster();
+        removing.toggle(idx);
+        ranks[idx] = rank;
+    }
+
+    for rank in seed_count..count {
+        let idx = pattern.largest_void();
+        pattern.toggle(idx);
+        ranks[idx] = rank;
+    }
+
+    ranks
+        .into_iter()
+        .map(|rank| (rank as f32 + 0.5) / count as f32 - 0.5)
+        .collect()
+}
@@ -0,0 +1,264 @@
+//! Frames Module
+//!
+//! Multi-frame inputs such as cubemaps and texture arrays.
+//!
+//! Shaders render one 2D frame at a time, so each face or array slice of a
+//! multi-frame input is run through the shader on its own, paired with the
+//! same frame of the other inputs. Single-frame inputs are reused for every
+//! frame. The rendered frames are then put back together in the input's
+//! layout when saving, optionally with a regenerated mip chain; formats that
+//! hold a single image, including EXR and KTX2, keep only the first frame and
+//! log a warning.
+//!
+//! Outputs carry the alpha mode they are saved with, so premultiplied outputs
+//! are converted frame by frame before the mip chain is built.
+
+use crate::components::texture_converter::ShaderConfig;
+use crate::components::texture_converter::alpha::AlphaMode;
+use crate::components::texture_converter::gpu_processor::process_images;
+use crate::components::texture_converter::jobs::JobProgress;
+use crate::components::texture_converter::types::RenderOptions;
+use crate::porter_image::{ImageBuffer, PorterImage};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Cubemap face names, in DDS face order
+const CUBEMAP_FACES: [&str; 6] = ["+X", "-X", "+Y", "-Y", "+Z", "-Z"];
+
+/// Frame layout of the inputs, taken from the first multi-frame input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameLayout {
+    pub count: usize,
+    pub cubemap: bool,
+    /// Rebuild a mip chain for every frame when saving
+    pub mipmaps: bool,
+}
+
+impl Default for FrameLayout {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            cubemap: false,
+            mipmaps: false,
+        }
+    }
+}
+
+impl FrameLayout {
+    /// Layout of the inputs in a set of slots
+    ///
+    /// Mipmaps are kept when any input had them.
+    pub fn of(images: &[Option<Arc<PorterImage>>]) -> Self {
+        let mipmaps = images.iter().flatten().any(|image| image.mip_count() > 1);
+
+        images
+            .iter()
+            .flatten()
+            .find(|image| image.frame_count() > 1)
+            .map(|image| Self {
+                count: image.frame_count(),
+                cubemap: image.is_cubemap(),
+                mipmaps,
+            })
+            .unwrap_or(Self {
+                mipmaps,
+                ..Self::default()
+            })
+    }
+
+    /// Whether there is more than one frame to process
+    pub fn is_multi_frame(&self) -> bool {
+        self.count > 1
+    }
+
+    /// Frames offered by the frame selector
+    pub fn choices(&self) -> Vec<FrameChoice> {
+        (0..self.count)
+            .map(|index| FrameChoice {
+                index,
+                cubemap: self.cubemap,
+            })
+            .collect()
+    }
+}
+
+/// One entry of the frame selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameChoice {
+    pub index: usize,
+    cubemap: bool,
+}
+
+impl std::fmt::Display for FrameChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match CUBEMAP_FACES.get(self.index).filter(|_| self.cubemap) {
+            Some(face) => write!(f, "Face {face}"),
+            None => write!(f, "Slice {}", self.index),
+        }
+    }
+}
+
+/// Every rendered frame of one output, ready to be saved
+#[derive(Debug, Clone)]
+pub struct FramedOutput {
+    /// Frames as rendered, with straight alpha
+    pub frames: Vec<ImageBuffer>,
+    /// Layout the frames are saved in
+    pub layout: FrameLayout,
+    pub alpha_mode: AlphaMode,
+}
+
+impl FramedOutput {
+    /// A single-frame output
+    ///
+    /// Frames of multi-frame layouts are pushed as they are rendered.
+    pub fn single(buffer: ImageBuffer, layout: FrameLayout, alpha_mode: AlphaMode) -> Self {
+        Self {
+            frames: vec![buffer],
+            layout,
+            alpha_mode,
+        }
+    }
+
+    /// Put the frames back together into one image in the output's alpha mode
+    pub fn into_porter_image(self) -> Result<PorterImage, String> {
+        let alpha_mode = self.alpha_mode;
+        let frames: Vec<ImageBuffer> = self
+            .frames
+            .into_iter()
+            .map(|frame| alpha_mode.from_straight(frame))
+            .collect();
+
+        let layout = self.layout;
+        match (frames.len(), layout.mipmaps) {
+            (1, false) => frames
+                .into_iter()
+                .next()
+                .expect("one frame")
+                .into_porter_image(),
+            _ => PorterImage::from_frames(&frames, layout.mipmaps, layout.cubemap),
+        }
+    }
+}
+
+/// Pick one frame of every multi-frame input
+///
+/// Single-frame inputs are used as they are, so they pair with every frame.
+/// Empty slots stay empty.
+pub fn frame_inputs(
+    images: &[Option<Arc<PorterImage>>],
+    frame: usize,
+) -> Result<Vec<Option<Arc<PorterImage>>>, String> {
+    images
+        .iter()
+        .map(|image| match image {
+            Some(image) if image.frame_count() > 1 => image.frame(frame).map(Arc::new).map(Some),
+            image => Ok(image.clone()),
+        })
+        .collect()
+}
+
+/// Render every frame of the inputs through a shader
+///
+/// Returns each output's frames with its description, in output order.
+pub async fn render_frames(
+    images: Vec<Option<Arc<PorterImage>>>,
+    layout: FrameLayout,
+    shader_config: ShaderConfig,
+    parameter_values: HashMap<String, f32>,
+    render: &RenderOptions,
+    progress: &JobProgress,
+) -> Result<Vec<(FramedOutput, String)>, String> {
+    let mut outputs: Vec<(FramedOutput, String)> = Vec::new();
+
+    for frame in 0..layout.count {
+        let rendered = process_images(
+            frame_inputs(&images, frame)?,
+            None,
+            shader_config.clone(),
+            parameter_values.clone(),
+            None,
+            render,
+            progress,
+        )
+        .await?;
+
+        if outputs.is_empty() {
+            outputs = rendered
+                .into_iter()
+                .map(|(buffer, description)| {
+                    let alpha_mode = shader_config.output_alpha_mode(&description);
+                    (
+                        FramedOutput::single(buffer, layout, alpha_mode),
+                        description,
+                    )
+                })
+                .collect();
+        } else {
+            for ((output, _), (buffer, _)) in outputs.iter_mut().zip(rendered) {
+                output.frames.push(buffer);
+            }
+        }
+    }
+
+    Ok(outputs)
+}
+
+/// Where a save gets every frame of the outputs from
+#[derive(Debug, Clone)]
+pub enum FrameSource {
+    /// Outputs already rendered from single-frame inputs
+    Rendered {
+        outputs: Vec<(FramedOutput, String)>,
+    },
+    /// Multi-frame inputs, rendered again one frame at a time
+    Inputs {
+        /// Input image of each slot
+        images: Vec<Option<Arc<PorterImage>>>,
+        layout: FrameLayout,
+        shader_config: ShaderConfig,
+        parameter_values: HashMap<String, f32>,
+        render: RenderOptions,
+    },
+}
+
+impl FrameSource {
+    /// Frames of the outputs with the given descriptions, in that order
+    pub async fn outputs(
+        self,
+        descriptions: &[String],
+        progress: &JobProgress,
+    ) -> Result<Vec<FramedOutput>, String> {
+        let mut outputs = match self {
+            FrameSource::Rendered { outputs } => outputs,
+            FrameSource::Inputs {
+                images,
+                layout,
+                shader_config,
+                parameter_values,
+                render,
+            } => {
+                render_frames(
+                    images,
+                    layout,
+                    shader_config,
+                    parameter_values,
+                    &render,
+                    progress,
+                )
+                .await?
+            }
+        };
+
+        descriptions
+            .iter()
+            .map(|description| {
+                outputs
+                    .iter()
+                    .position(|(_, output)| output == description)
+                    .map(|idx| outputs.swap_remove(idx).0)
+                    .ok_or_else(|| format!("No output named {description}"))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,281 @@
+//! GPU Cache Module
+//!
+//! GPU resources kept between processing runs.
+//!
+//! Re-processing after a parameter change only needs a new uniform buffer,
+//! but compiling shaders, building pipelines and uploading inputs cost far
+//! more than the render itself. Each shader keeps a context with its compiled
+//! modules, layouts, a pipeline per entry point and target format and its
+//! uniform buffer, which is rebuilt only when the shader file changes on
+//! disk. Input textures are
+//! kept per slot and uploaded again only when the slot's generation changes.
+//! Staging buffers for readback are pooled here too.
+
+use crate::components::texture_converter::ShaderConfig;
+use crate::components::texture_converter::alpha::AlphaMode;
+use crate::components::texture_converter::builtins::create_builtins_layout;
+use crate::components::texture_converter::dither::Dithering;
+use crate::components::texture_converter::gpu_processor::{
+    GpuResult, create_fragment_shader, create_image_texture, create_output_pipeline,
+    create_parameters_layout, create_pipeline_layout, create_texture_bind_group_layout,
+    create_vertex_shader, load_shader_code, parameter_uniform_data,
+};
+use crate::components::texture_converter::readback::StagingPool;
+use crate::porter_image::PorterImage;
+use futures::lock::{Mutex, MutexGuard};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::SystemTime;
+
+static CACHE: LazyLock<Mutex<GpuCache>> = LazyLock::new(Default::default);
+
+/// Lock the shared cache
+///
+/// Hold the guard for the whole run: the cached uniform buffer is rewritten
+/// by every run, so two runs of one shader must not overlap. The lock is
+/// awaited, since runs hold it while they wait for the GPU.
+pub async fn lock() -> MutexGuard<'static, GpuCache> {
+    CACHE.lock().await
+}
+
+/// Compiled resources for one shader file
+#[derive(Debug)]
+pub struct ShaderContext {
+    /// Modification time of the shader file when it was compiled
+    modified: Option<SystemTime>,
+    input_count: usize,
+    parameter_count: usize,
+    pub vertex_shader: wgpu::ShaderModule,
+    pub fragment_shader: wgpu::ShaderModule,
+    pub texture_layout: wgpu::BindGroupLayout,
+    pub pipeline_layout: wgpu::PipelineLayout,
+    pub builtins_layout: wgpu::BindGroupLayout,
+    params_layout: Option<wgpu::BindGroupLayout>,
+    /// Uniform buffer and its bind group, created on first use
+    params: Option<(wgpu::Buffer, wgpu::BindGroup)>,
+    /// Group 1 for shaders without parameters
+    empty_params: wgpu::BindGroup,
+    /// Render pipelines keyed by fragment entry point and target format
+    pipelines: HashMap<(String, wgpu::TextureFormat), wgpu::RenderPipeline>,
+}
+
+impl ShaderContext {
+    /// Compile a shader and build its layouts
+    fn new(device: &wgpu::Device, shader_config: &ShaderConfig) -> GpuResult<Self> {
+        let code = load_shader_code(&shader_config.shader_path)?;
+        let fragment_shader = create_fragment_shader(device, &code, &shader_config.shader.name)?;
+        let texture_layout = create_texture_bind_group_layout(device, shader_config);
+        let params_layout = create_parameters_layout(device, shader_config);
+        let builtins_layout = create_builtins_layout(device);
+
+        // Shaders without parameters get an empty group 1, so the built-ins
+        // are always at group 2
+        let empty_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Empty Parameters Bind Group Layout"),
+            entries: &[],
+        });
+        let empty_params = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Empty Parameters Bind Group"),
+            layout: &empty_layout,
+            entries: &[],
+        });
+        let pipeline_layout = create_pipeline_layout(
+            device,
+            &texture_layout,
+            params_layout.as_ref().unwrap_or(&empty_layout),
+            &builtins_layout,
+        )?;
+
+        tracing::info!("Compiled shader {}", shader_config.shader_path.display());
+
+        Ok(Self {
+            modified: modified_time(&shader_config.shader_path),
+            input_count: shader_config.inputs.len(),
+            parameter_count: shader_config.parameters.len(),
+            vertex_shader: create_vertex_shader(device),
+            fragment_shader,
+            texture_layout,
+            pipeline_layout,
+            builtins_layout,
+            params_layout,
+            params: None,
+            empty_params,
+            pipelines: HashMap::new(),
+        })
+    }
+
+    /// Whether the context no longer matches the shader file or its config
+    fn is_stale(&self, shader_config: &ShaderConfig) -> bool {
+        self.modified != modified_time(&shader_config.shader_path)
+            || self.input_count != shader_config.inputs.len()
+            || self.parameter_count != shader_config.parameters.len()
+    }
+
+    /// Pipeline for each output, in output order
+    ///
+    /// Dithered outputs render into a float target, so they get their own
+    /// pipeline.
+    pub fn pipelines(
+        &mut self,
+        device: &wgpu::Device,
+        shader_config: &ShaderConfig,
+        dithering: &Dithering,
+    ) -> Vec<wgpu::RenderPipeline> {
+        shader_config
+            .outputs
+            .iter()
+            .map(|output_config| {
+                let format = dithering
+                    .for_output(&output_config.description)
+                    .render_format();
+                self.pipelines
+                    .entry((output_config.entry_point.clone(), format))
+                    .or_insert_with(|| {
+                        create_output_pipeline(
+                            device,
+                            &self.vertex_shader,
+                            &self.fragment_shader,
+                            &self.pipeline_layout,
+                            output_config,
+                            format,
+                        )
+                    })
+                    .clone()
+            })
+            .collect()
+    }
+
+    /// Write parameter values into the uniform buffer
+    ///
+    /// Returns the bind group to render with, which is empty if the shader
+    /// has no parameters.
+    pub fn parameters_bind_group(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shader_config: &ShaderConfig,
+        parameter_values: &HashMap<String, f32>,
+    ) -> wgpu::BindGroup {
+        let Some(layout) = self.params_layout.as_ref() else {
+            return self.empty_params.clone();
+        };
+        let uniform_data = parameter_uniform_data(shader_config, parameter_values);
+
+        let (buffer, bind_group) = self.params.get_or_insert_with(|| {
+            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Parameters Uniform Buffer"),
+                size: uniform_data.len() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Parameters Bind Group"),
+                layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+            (buffer, bind_group)
+        });
+
+        queue.write_buffer(buffer, 0, &uniform_data);
+        bind_group.clone()
+    }
+}
+
+/// An uploaded input texture
+#[derive(Debug)]
+struct CachedInput {
+    generation: u64,
+    /// Alpha mode the pixels were converted from on upload
+    alpha_mode: AlphaMode,
+    texture: wgpu::Texture,
+}
+
+/// GPU resources shared between processing runs
+#[derive(Debug, Default)]
+pub struct GpuCache {
+    /// Shader contexts keyed by shader file
+    shaders: HashMap<PathBuf, ShaderContext>,
+    /// Input textures keyed by slot index
+    inputs: HashMap<usize, CachedInput>,
+    /// Staging buffers for reading outputs back
+    staging: StagingPool,
+}
+
+impl GpuCache {
+    /// Context for a shader, compiling it if needed
+    pub fn shader_context(
+        &mut self,
+        device: &wgpu::Device,
+        shader_config: &ShaderConfig,
+    ) -> GpuResult<&mut ShaderContext> {
+        let path = &shader_config.shader_path;
+        if self
+            .shaders
+            .get(path)
+            .is_none_or(|context| context.is_stale(shader_config))
+        {
+            let context = ShaderContext::new(device, shader_config)?;
+            self.shaders.insert(path.clone(), context);
+        }
+
+        Ok(self
+            .shaders
+            .get_mut(path)
+            .expect("shader context was just inserted"))
+    }
+
+    /// Texture for an input image, uploading it unless the slot's cached
+    /// texture has the same generation and alpha mode
+    ///
+    /// Generation 0 marks a slot that was never loaded, so it is never cached.
+    pub fn input_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        slot: usize,
+        generation: u64,
+        image: &Arc<PorterImage>,
+        alpha_mode: AlphaMode,
+    ) -> GpuResult<wgpu::Texture> {
+        if let Some(cached) = self.inputs.get(&slot).filter(|cached| {
+            generation != 0 && cached.generation == generation && cached.alpha_mode == alpha_mode
+        }) {
+            return Ok(cached.texture.clone());
+        }
+
+        let texture = create_image_texture(device, queue, image, slot, alpha_mode)?;
+        if generation != 0 {
+            self.inputs.insert(
+                slot,
+                CachedInput {
+                    generation,
+                    alpha_mode,
+                    texture: texture.clone(),
+                },
+            );
+        }
+
+        Ok(texture)
+    }
+
+    /// Staging buffers shared by every run
+    pub fn staging_pool(&mut self) -> &mut StagingPool {
+        &mut self.staging
+    }
+
+    /// Drop cached inputs for slots at or beyond `count`
+    pub fn retain_inputs(&mut self, count: usize) {
+        self.inputs.retain(|slot, _| *slot < count);
+    }
+}
+
+/// Modification time of a file, if it can be read
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
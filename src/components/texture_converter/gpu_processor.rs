@@ -5,11 +5,27 @@
 //! This module provides functionality to:
 //! - Execute WGSL shaders on input textures
 //! - Manage GPU resources (textures, buffers, pipelines)
-//! - Handle multiple shader outputs
-//! - Read back processed results from GPU to CPU
-
+//! - Handle multiple shader outputs, each at its own size
+//! - Split images larger than the GPU texture limit into tiles
+//! - Read back processed results from GPU to CPU through pooled staging buffers
+//! - Render dithered outputs at float precision and quantise them on readback
+//! - Bind the built-in uniforms every shader can read
+//! - Report progress and stop between stages when a job is cancelled
+//!
+//! Compiled shaders, pipelines and uploaded inputs are reused between runs
+//! through the `gpu_cache` module.
+
+use crate::components::texture_converter::alpha::AlphaMode;
+use crate::components::texture_converter::builtins::{BUILTINS_GROUP, InputSizes};
+use crate::components::texture_converter::dither::Dithering;
+use crate::components::texture_converter::gpu_cache::{self, GpuCache};
+use crate::components::texture_converter::jobs::{JobProgress, JobStage};
+use crate::components::texture_converter::readback::{self, StagingPool};
+use crate::components::texture_converter::tiling::{Tile, plan_tiles, tile_source_size};
+use crate::components::texture_converter::types::RenderOptions;
 use crate::components::texture_converter::{FULLSCREEN_QUAD_VERTEX_SHADER, ShaderConfig};
 use crate::porter_image::{ImageBuffer, PorterImage};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -19,37 +35,75 @@ pub type GpuResult<T> = Result<T, String>;
 /// Process images using the specified shader configuration
 ///
 /// # Arguments
-/// * `images` - Input images to process
+/// * `images` - Input image of each shader input slot, None for empty slots
+/// * `input_generations` - Generation of each slot, so unchanged inputs
+///   reuse their uploaded textures; None for images that won't be seen again
 /// * `shader_config` - Shader configuration with inputs/outputs/parameters
 /// * `parameter_values` - User-defined parameter values for the shader
+/// * `max_output_dimension` - Cap on the output size for quick previews; the
+///   inputs are sampled down on the GPU. Ignored for tiled images
+/// * `render` - Size of each output and how it is quantised to 8 bits
+/// * `progress` - Job to report stages to; a cancelled job stops processing
 ///
 /// # Returns
 /// * `Ok(Vec<(ImageBuffer, String)>)` - Processed outputs with descriptions
 /// * `Err(String)` - Error message if processing fails
 pub async fn process_images(
-    images: Vec<Arc<PorterImage>>,
+    images: Vec<Option<Arc<PorterImage>>>,
+    input_generations: Option<Vec<u64>>,
     shader_config: ShaderConfig,
     parameter_values: HashMap<String, f32>,
+    max_output_dimension: Option<u32>,
+    render: &RenderOptions,
+    progress: &JobProgress,
 ) -> GpuResult<Vec<(ImageBuffer, String)>> {
-    if images.is_empty() {
-        return Err("No images provided".to_string());
-    }
-
-    // Load shader code
-    let fragment_shader_code = load_shader_code(&shader_config.shader_path)?;
+    let first_image = images
+        .iter()
+        .flatten()
+        .next()
+        .ok_or_else(|| "No images provided".to_string())?;
 
     // Get GPU resources
     let gpu = porter_gpu::gpu_instance();
     let device = gpu.device();
     let queue = gpu.queue();
 
-    // Create shader modules
-    let vertex_shader = create_vertex_shader(device);
-    let fragment_shader =
-        create_fragment_shader(device, &fragment_shader_code, &shader_config.shader.name)?;
+    // Compiled shaders and pipelines are reused; only the uniforms change
+    let mut cache = gpu_cache::lock().await;
+    let context = cache.shader_context(device, &shader_config)?;
+    let pipelines = context.pipelines(device, &shader_config, &render.dithering);
+    let params_bind_group =
+        context.parameters_bind_group(device, queue, &shader_config, &parameter_values);
+    let texture_bind_group_layout = context.texture_layout.clone();
+    let builtins_layout = context.builtins_layout.clone();
+
+    // Images beyond the GPU texture limit are rendered in tiles
+    let max_dimension = device.limits().max_texture_dimension_2d;
+    let exceeds_limit = images.iter().flatten().any(|img| {
+        let (w, h) = img.dimensions();
+        w > max_dimension || h > max_dimension
+    });
+
+    if exceeds_limit {
+        return process_tiled(
+            device,
+            queue,
+            cache.staging_pool(),
+            &pipelines,
+            &texture_bind_group_layout,
+            &params_bind_group,
+            &builtins_layout,
+            &images,
+            &shader_config,
+            render,
+            max_dimension,
+            progress,
+        )
+        .await;
+    }
 
     // Get dimensions from first image
-    let (width, height) = images[0].dimensions();
+    let (width, height) = first_image.dimensions();
     let texture_size = wgpu::Extent3d {
         width,
         height,
@@ -57,44 +111,309 @@ pub async fn process_images(
     };
 
     // Create input textures and resources
-    let (_input_textures, input_views, input_samplers) =
-        create_input_textures(device, queue, &images, &shader_config, texture_size)?;
+    progress.stage(JobStage::Upload)?;
+    let (input_textures, input_views, input_samplers) = create_input_textures(
+        device,
+        queue,
+        &mut cache,
+        &images,
+        input_generations.as_deref(),
+        &shader_config,
+        texture_size,
+    )?;
 
     // Create bind group for textures
-    let (texture_bind_group_layout, texture_bind_group) =
-        create_texture_bind_group(device, &shader_config, &input_views, &input_samplers)?;
-
-    // Create parameters bind group if needed
-    let (params_bind_group_layout, params_bind_group) =
-        create_parameters_bind_group(device, queue, &shader_config, &parameter_values)?;
-
-    // Create pipeline layout
-    let pipeline_layout = create_pipeline_layout(
+    let texture_bind_group = create_texture_bind_group(
         device,
         &texture_bind_group_layout,
-        params_bind_group_layout.as_ref(),
-    )?;
+        &input_views,
+        &input_samplers,
+    );
 
-    // Process each output
+    // Process each output at its own size, within the GPU limit and any
+    // preview cap
+    let output_sizes: Vec<wgpu::Extent3d> = shader_config
+        .outputs
+        .iter()
+        .map(|output_config| {
+            let (width, height) = render.sizes.output_size(output_config, (width, height));
+            let size = capped_size(
+                wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                max_dimension,
+            );
+            if (size.width, size.height) != (width, height) {
+                tracing::warn!(
+                    "{} capped to {}x{} from {}x{} by the GPU texture limit",
+                    output_config.description,
+                    size.width,
+                    size.height,
+                    width,
+                    height
+                );
+            }
+            max_output_dimension
+                .map(|max| capped_size(size, max))
+                .unwrap_or(size)
+        })
+        .collect();
+    let provided: Vec<bool> = images.iter().map(Option::is_some).collect();
+    let input_sizes = InputSizes::new(&input_textures, &provided);
     let output_buffers = process_all_outputs(
         device,
         queue,
-        &vertex_shader,
-        &fragment_shader,
-        &pipeline_layout,
+        cache.staging_pool(),
+        &pipelines,
         &shader_config,
-        texture_size,
+        &render.dithering,
+        &output_sizes,
+        &input_sizes,
         &texture_bind_group,
-        params_bind_group.as_ref(),
-    )?;
+        &params_bind_group,
+        &builtins_layout,
+        progress,
+    )
+    .await?;
 
     Ok(output_buffers)
 }
 
+/// Process images that exceed the GPU texture limit
+///
+/// Splits the output into overlapping tiles no larger than `max_dimension`,
+/// renders every output for each tile using cropped inputs, and stitches the
+/// tile interiors into full-size output buffers. Outputs with another size
+/// are resized on the CPU once stitched.
+#[allow(clippy::too_many_arguments)]
+async fn process_tiled(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    staging: &mut StagingPool,
+    pipelines: &[wgpu::RenderPipeline],
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    params_bind_group: &wgpu::BindGroup,
+    builtins_layout: &wgpu::BindGroupLayout,
+    images: &[Option<Arc<PorterImage>>],
+    shader_config: &ShaderConfig,
+    render: &RenderOptions,
+    max_dimension: u32,
+    progress: &JobProgress,
+) -> GpuResult<Vec<(ImageBuffer, String)>> {
+    let (width, height) = images
+        .iter()
+        .flatten()
+        .next()
+        .ok_or_else(|| "No images provided".to_string())?
+        .dimensions();
+    let tiles = plan_tiles(
+        width,
+        height,
+        max_dimension,
+        shader_config.shader.tile_apron,
+    )?;
+
+    tracing::info!(
+        "Processing {}x{} image in {} tiles (limit {}px, apron {}px)",
+        width,
+        height,
+        tiles.len(),
+        max_dimension,
+        shader_config.shader.tile_apron
+    );
+
+    // Decode every input once so tiles can be cropped from CPU memory,
+    // resampling inputs whose crops wouldn't fit a texture
+    let sources = images
+        .iter()
+        .enumerate()
+        .map(|(idx, img)| {
+            img.as_ref()
+                .map(|img| {
+                    let buffer =
+                        to_straight_rgba8_buffer(img, shader_config.input_alpha_mode(idx))?;
+                    let (source_width, source_height) =
+                        tile_source_size((width, height), buffer.dimensions(), max_dimension);
+                    if (source_width, source_height) == buffer.dimensions() {
+                        Ok(buffer)
+                    } else {
+                        buffer.resized(source_width, source_height)
+                    }
+                })
+                .transpose()
+        })
+        .collect::<GpuResult<Vec<_>>>()?;
+    let provided: Vec<bool> = sources.iter().map(Option::is_some).collect();
+
+    let mut outputs: Vec<ImageBuffer> = shader_config
+        .outputs
+        .iter()
+        .map(|_| ImageBuffer::from_pixel(width, height, [0, 0, 0, 0]))
+        .collect();
+
+    for (tile_idx, tile) in tiles.iter().enumerate() {
+        progress.step(JobStage::Upload, tile_idx + 1, tiles.len())?;
+
+        let tile_size = wgpu::Extent3d {
+            width: tile.width,
+            height: tile.height,
+            depth_or_array_layers: 1,
+        };
+
+        let (input_textures, input_views, input_samplers) = create_tile_input_textures(
+            device,
+            queue,
+            &sources,
+            shader_config,
+            tile,
+            (width, height),
+        )?;
+
+        let texture_bind_group = create_texture_bind_group(
+            device,
+            texture_bind_group_layout,
+            &input_views,
+            &input_samplers,
+        );
+
+        let (inner_offset_x, inner_offset_y) = tile.inner_offset();
+
+        let tile_buffers = render_outputs(
+            device,
+            queue,
+            staging,
+            pipelines,
+            shader_config,
+            &render.dithering,
+            &vec![tile_size; pipelines.len()],
+            &InputSizes::new(&input_textures, &provided),
+            (tile.x, tile.y),
+            &texture_bind_group,
+            params_bind_group,
+            builtins_layout,
+            progress,
+        )
+        .await?;
+
+        for (tile_buffer, output) in tile_buffers.iter().zip(outputs.iter_mut()) {
+            output.copy_region_from(
+                tile_buffer,
+                inner_offset_x,
+                inner_offset_y,
+                tile.inner_width,
+                tile.inner_height,
+                tile.inner_x,
+                tile.inner_y,
+            );
+        }
+    }
+
+    outputs
+        .into_iter()
+        .zip(shader_config.outputs.iter())
+        .map(|(buffer, output_config)| {
+            let (output_width, output_height) =
+                render.sizes.output_size(output_config, (width, height));
+            let buffer = if (output_width, output_height) == (width, height) {
+                buffer
+            } else {
+                buffer.resized(output_width, output_height)?
+            };
+            Ok((buffer, output_config.description.clone()))
+        })
+        .collect()
+}
+
+/// Create input textures for a single tile
+///
+/// Crops each decoded input to the tile region and uploads it. Optional
+/// inputs that weren't provided get a white placeholder of the tile size.
+fn create_tile_input_textures(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    sources: &[Option<ImageBuffer>],
+    shader_config: &ShaderConfig,
+    tile: &Tile,
+    output_size: (u32, u32),
+) -> GpuResult<(
+    Vec<wgpu::Texture>,
+    Vec<wgpu::TextureView>,
+    Vec<wgpu::Sampler>,
+)> {
+    let tile_size = wgpu::Extent3d {
+        width: tile.width,
+        height: tile.height,
+        depth_or_array_layers: 1,
+    };
+
+    let mut input_textures = Vec::new();
+    let mut input_views = Vec::new();
+    let mut input_samplers = Vec::new();
+
+    for (idx, _input_config) in shader_config.inputs.iter().enumerate() {
+        let texture = if let Some(source) = sources.get(idx).and_then(Option::as_ref) {
+            let (x, y, w, h) = tile.source_region(output_size, source.dimensions());
+            let cropped = source.crop(x, y, w, h);
+            create_rgba8_texture(
+                device,
+                queue,
+                cropped.as_raw(),
+                cropped.width(),
+                cropped.height(),
+                &format!("Input Texture {idx} (Tile)"),
+            )
+        } else {
+            create_placeholder_texture(device, queue, tile_size, idx)?
+        };
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = create_sampler(device, idx);
+
+        input_textures.push(texture);
+        input_views.push(view);
+        input_samplers.push(sampler);
+    }
+
+    Ok((input_textures, input_views, input_samplers))
+}
+
+/// Scale a size down to fit within `max` pixels on its longest side
+fn capped_size(size: wgpu::Extent3d, max: u32) -> wgpu::Extent3d {
+    let longest = size.width.max(size.height);
+    if longest <= max {
+        return size;
+    }
+
+    let scale = max as f64 / longest as f64;
+    wgpu::Extent3d {
+        width: ((size.width as f64 * scale).round() as u32).max(1),
+        height: ((size.height as f64 * scale).round() as u32).max(1),
+        depth_or_array_layers: 1,
+    }
+}
+
+/// Convert an image to a tightly packed, straight-alpha RGBA8 buffer
+fn to_straight_rgba8_buffer(
+    img: &Arc<PorterImage>,
+    alpha_mode: AlphaMode,
+) -> GpuResult<ImageBuffer> {
+    let buffer = img
+        .to_rgba8_image_buffer()
+        .map_err(|e| format!("Failed to convert image to RGBA8: {e}"))?;
+
+    match alpha_mode.to_straight(buffer.as_raw()) {
+        Cow::Borrowed(_) => Ok(buffer),
+        Cow::Owned(data) => ImageBuffer::from_raw(buffer.width(), buffer.height(), data)
+            .ok_or_else(|| "Failed to create ImageBuffer".to_string()),
+    }
+}
+
 /// Load shader WGSL code from file
 ///
 /// Reads the shader source code from disk.
-fn load_shader_code(shader_path: &std::path::Path) -> GpuResult<String> {
+pub fn load_shader_code(shader_path: &std::path::Path) -> GpuResult<String> {
     std::fs::read_to_string(shader_path)
         .map_err(|e| format!("Failed to load shader from {shader_path:?}: {e}"))
 }
@@ -102,7 +421,7 @@ fn load_shader_code(shader_path: &std::path::Path) -> GpuResult<String> {
 /// Create vertex shader module from embedded source
 ///
 /// Uses the fullscreen quad vertex shader for all processing operations.
-fn create_vertex_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
+pub fn create_vertex_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
     device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("Fullscreen Quad Vertex Shader"),
         source: wgpu::ShaderSource::Wgsl(
@@ -116,7 +435,7 @@ fn create_vertex_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
 /// Create fragment shader module from loaded WGSL code
 ///
 /// Compiles the user-provided fragment shader.
-fn create_fragment_shader(
+pub fn create_fragment_shader(
     device: &wgpu::Device,
     code: &str,
     name: &str,
@@ -130,11 +449,15 @@ fn create_fragment_shader(
 /// Create input textures for all shader inputs
 ///
 /// Creates GPU textures for loaded images and white placeholders
-/// for optional inputs that weren't provided.
+/// for optional inputs that weren't provided. Images keep their slot index,
+/// so each is uploaded with its own input's alpha mode, and images with a
+/// generation are taken from the cache when their slot hasn't changed.
 fn create_input_textures(
     device: &wgpu::Device,
     queue: &wgpu::Queue,
-    images: &[Arc<PorterImage>],
+    cache: &mut GpuCache,
+    images: &[Option<Arc<PorterImage>>],
+    generations: Option<&[u64]>,
     shader_config: &ShaderConfig,
     texture_size: wgpu::Extent3d,
 ) -> GpuResult<(
@@ -148,8 +471,24 @@ fn create_input_textures(
 
     // Create textures for all defined inputs (including placeholders for optional ones)
     for (idx, _input_config) in shader_config.inputs.iter().enumerate() {
-        let texture = if idx < images.len() {
-            create_image_texture(device, queue, &images[idx], idx)?
+        let texture = if let Some(image) = images.get(idx).and_then(Option::as_ref) {
+            match generations.and_then(|generations| generations.get(idx)) {
+                Some(&generation) => cache.input_texture(
+                    device,
+                    queue,
+                    idx,
+                    generation,
+                    image,
+                    shader_config.input_alpha_mode(idx),
+                )?,
+                None => create_image_texture(
+                    device,
+                    queue,
+                    image,
+                    idx,
+                    shader_config.input_alpha_mode(idx),
+                )?,
+            }
         } else {
             create_placeholder_texture(device, queue, texture_size, idx)?
         };
@@ -162,28 +501,64 @@ fn create_input_textures(
         input_samplers.push(sampler);
     }
 
+    if generations.is_some() {
+        cache.retain_inputs(images.len());
+    }
+
     Ok((input_textures, input_views, input_samplers))
 }
 
 /// Create a GPU texture from an image
 ///
-/// Converts a PorterImage to RGBA8 format and uploads to GPU.
-fn create_image_texture(
+/// Converts a PorterImage to RGBA8 format with straight alpha and uploads
+/// it to the GPU.
+pub fn create_image_texture(
     device: &wgpu::Device,
     queue: &wgpu::Queue,
     img: &Arc<PorterImage>,
     idx: usize,
+    alpha_mode: AlphaMode,
 ) -> GpuResult<wgpu::Texture> {
     let (img_width, img_height) = img.dimensions();
-    let img_texture_size = wgpu::Extent3d {
-        width: img_width,
-        height: img_height,
+
+    // Convert and upload image data
+    let mut img_mut = (**img).clone();
+    if let Err(e) = img_mut.convert_to_rgba8() {
+        return Err(format!("Failed to convert image to RGBA8: {e}"));
+    }
+    let data = img_mut
+        .raw_buffer()
+        .map_err(|e| format!("Failed to get raw buffer: {e}"))?;
+    let data = alpha_mode.to_straight(data);
+
+    Ok(create_rgba8_texture(
+        device,
+        queue,
+        &data,
+        img_width,
+        img_height,
+        &format!("Input Texture {idx}"),
+    ))
+}
+
+/// Create an RGBA8 GPU texture and upload tightly packed pixel data
+fn create_rgba8_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    data: &[u8],
+    width: u32,
+    height: u32,
+    label: &str,
+) -> wgpu::Texture {
+    let texture_size = wgpu::Extent3d {
+        width,
+        height,
         depth_or_array_layers: 1,
     };
 
     let texture = device.create_texture(&wgpu::TextureDescriptor {
-        label: Some(&format!("Input Texture {idx}")),
-        size: img_texture_size,
+        label: Some(label),
+        size: texture_size,
         mip_level_count: 1,
         sample_count: 1,
         dimension: wgpu::TextureDimension::D2,
@@ -192,28 +567,18 @@ fn create_image_texture(
         view_formats: &[],
     });
 
-    // Convert and upload image data
-    let mut img_mut = (**img).clone();
-    if let Err(e) = img_mut.convert_to_rgba8() {
-        return Err(format!("Failed to convert image to RGBA8: {e}"));
-    }
-    let data = img_mut
-        .raw_buffer()
-        .map_err(|e| format!("Failed to get raw buffer: {e}"))?;
-    let rgba_data: Vec<u8> = data.to_vec();
-
     queue.write_texture(
         texture.as_image_copy(),
-        &rgba_data,
+        data,
         wgpu::TexelCopyBufferLayout {
             offset: 0,
-            bytes_per_row: Some(4 * img_width),
-            rows_per_image: Some(img_height),
+            bytes_per_row: Some(4 * width),
+            rows_per_image: Some(height),
         },
-        img_texture_size,
+        texture_size,
     );
 
-    Ok(texture)
+    texture
 }
 
 /// Create a white placeholder texture for optional inputs
@@ -236,7 +601,7 @@ fn create_placeholder_texture(
         view_formats: &[],
     });
 
-    let white_data = vec![255u8; (texture_size.width * texture_size.height * 4) as usize];
+    let white_data = vec![255u8; texture_size.width as usize * texture_size.height as usize * 4];
     queue.write_texture(
         texture.as_image_copy(),
         &white_data,
@@ -267,15 +632,13 @@ fn create_sampler(device: &wgpu::Device, idx: usize) -> wgpu::Sampler {
     })
 }
 
-/// Create bind group layout and bind group for textures
+/// Create bind group layout for textures
 ///
-/// Sets up GPU bindings for all input textures and their samplers.
-fn create_texture_bind_group(
+/// Declares a texture and sampler binding for every shader input.
+pub fn create_texture_bind_group_layout(
     device: &wgpu::Device,
     shader_config: &ShaderConfig,
-    input_views: &[wgpu::TextureView],
-    input_samplers: &[wgpu::Sampler],
-) -> GpuResult<(wgpu::BindGroupLayout, wgpu::BindGroup)> {
+) -> wgpu::BindGroupLayout {
     // Create bind group layout for all shader inputs
     let mut layout_entries = Vec::new();
     for i in 0..shader_config.inputs.len() {
@@ -298,12 +661,21 @@ fn create_texture_bind_group(
         });
     }
 
-    let texture_bind_group_layout =
-        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Texture Bind Group Layout"),
-            entries: &layout_entries,
-        });
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Texture Bind Group Layout"),
+        entries: &layout_entries,
+    })
+}
 
+/// Create bind group for textures
+///
+/// Binds all input textures and their samplers to the given layout.
+fn create_texture_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    input_views: &[wgpu::TextureView],
+    input_samplers: &[wgpu::Sampler],
+) -> wgpu::BindGroup {
     // Create bind group entries for textures
     let mut bind_group_entries = Vec::new();
     for (i, (view, sampler)) in input_views.iter().zip(input_samplers.iter()).enumerate() {
@@ -318,30 +690,48 @@ fn create_texture_bind_group(
         });
     }
 
-    let texture_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
         label: Some("Texture Bind Group"),
-        layout: &texture_bind_group_layout,
+        layout,
         entries: &bind_group_entries,
-    });
-
-    Ok((texture_bind_group_layout, texture_bind_group))
+    })
 }
 
-/// Create bind group for shader parameters if needed
+/// Create bind group layout for shader parameters if needed
 ///
-/// Creates a uniform buffer containing all parameter values.
 /// Returns None if shader has no parameters.
-fn create_parameters_bind_group(
+pub fn create_parameters_layout(
     device: &wgpu::Device,
-    queue: &wgpu::Queue,
     shader_config: &ShaderConfig,
-    parameter_values: &HashMap<String, f32>,
-) -> GpuResult<(Option<wgpu::BindGroupLayout>, Option<wgpu::BindGroup>)> {
+) -> Option<wgpu::BindGroupLayout> {
     if shader_config.parameters.is_empty() {
-        return Ok((None, None));
+        return None;
     }
 
-    // Build uniform data from parameters
+    Some(
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Parameters Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        }),
+    )
+}
+
+/// Build uniform data from parameter values
+///
+/// Values are written as f32 in declaration order and padded to 16 bytes.
+pub fn parameter_uniform_data(
+    shader_config: &ShaderConfig,
+    parameter_values: &HashMap<String, f32>,
+) -> Vec<u8> {
     let mut uniform_data = Vec::new();
     for param in &shader_config.parameters {
         let value = parameter_values
@@ -356,59 +746,23 @@ fn create_parameters_bind_group(
         uniform_data.push(0);
     }
 
-    let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("Parameters Uniform Buffer"),
-        size: uniform_data.len() as u64,
-        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        mapped_at_creation: false,
-    });
-
-    queue.write_buffer(&params_buffer, 0, &uniform_data);
-
-    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-        label: Some("Parameters Bind Group Layout"),
-        entries: &[wgpu::BindGroupLayoutEntry {
-            binding: 0,
-            visibility: wgpu::ShaderStages::FRAGMENT,
-            ty: wgpu::BindingType::Buffer {
-                ty: wgpu::BufferBindingType::Uniform,
-                has_dynamic_offset: false,
-                min_binding_size: None,
-            },
-            count: None,
-        }],
-    });
-
-    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: Some("Parameters Bind Group"),
-        layout: &layout,
-        entries: &[wgpu::BindGroupEntry {
-            binding: 0,
-            resource: params_buffer.as_entire_binding(),
-        }],
-    });
-
-    Ok((Some(layout), Some(bind_group)))
+    uniform_data
 }
 
 /// Create pipeline layout
 ///
-/// Combines texture and parameter bind group layouts.
-fn create_pipeline_layout(
+/// Combines the texture, parameter and built-in bind group layouts, in
+/// group order.
+pub fn create_pipeline_layout(
     device: &wgpu::Device,
     texture_layout: &wgpu::BindGroupLayout,
-    params_layout: Option<&wgpu::BindGroupLayout>,
+    params_layout: &wgpu::BindGroupLayout,
+    builtins_layout: &wgpu::BindGroupLayout,
 ) -> GpuResult<wgpu::PipelineLayout> {
-    let bind_group_layouts: Vec<&wgpu::BindGroupLayout> = if let Some(params) = params_layout {
-        vec![texture_layout, params]
-    } else {
-        vec![texture_layout]
-    };
-
     Ok(
         device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Pipeline Layout"),
-            bind_group_layouts: &bind_group_layouts,
+            bind_group_layouts: &[texture_layout, params_layout, builtins_layout],
             push_constant_ranges: &[],
         }),
     )
@@ -416,53 +770,120 @@ fn create_pipeline_layout(
 
 /// Process all outputs defined in the shader configuration
 #[allow(clippy::too_many_arguments)]
-fn process_all_outputs(
+async fn process_all_outputs(
     device: &wgpu::Device,
     queue: &wgpu::Queue,
-    vertex_shader: &wgpu::ShaderModule,
-    fragment_shader: &wgpu::ShaderModule,
-    pipeline_layout: &wgpu::PipelineLayout,
+    staging: &mut StagingPool,
+    pipelines: &[wgpu::RenderPipeline],
     shader_config: &ShaderConfig,
-    texture_size: wgpu::Extent3d,
+    dithering: &Dithering,
+    output_sizes: &[wgpu::Extent3d],
+    input_sizes: &InputSizes,
     texture_bind_group: &wgpu::BindGroup,
-    params_bind_group: Option<&wgpu::BindGroup>,
+    params_bind_group: &wgpu::BindGroup,
+    builtins_layout: &wgpu::BindGroupLayout,
+    progress: &JobProgress,
 ) -> GpuResult<Vec<(ImageBuffer, String)>> {
-    let mut output_buffers = Vec::new();
+    let output_buffers = render_outputs(
+        device,
+        queue,
+        staging,
+        pipelines,
+        shader_config,
+        dithering,
+        output_sizes,
+        input_sizes,
+        (0, 0),
+        texture_bind_group,
+        params_bind_group,
+        builtins_layout,
+        progress,
+    )
+    .await?;
+
+    Ok(output_buffers
+        .into_iter()
+        .zip(shader_config.outputs.iter())
+        .map(|(buffer, output_config)| (buffer, output_config.description.clone()))
+        .collect())
+}
+
+/// Render every output pipeline and read the results back together
+///
+/// All render passes and staging copies go into one encoder, so the outputs
+/// are rendered in a single submission and read back with one wait. Each
+/// output renders at its entry in `output_sizes`, and gets its own built-ins
+/// holding its size and index. `origin` is the render's position in the full
+/// image.
+#[allow(clippy::too_many_arguments)]
+async fn render_outputs(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    staging: &mut StagingPool,
+    pipelines: &[wgpu::RenderPipeline],
+    shader_config: &ShaderConfig,
+    dithering: &Dithering,
+    output_sizes: &[wgpu::Extent3d],
+    input_sizes: &InputSizes,
+    origin: (u32, u32),
+    texture_bind_group: &wgpu::BindGroup,
+    params_bind_group: &wgpu::BindGroup,
+    builtins_layout: &wgpu::BindGroupLayout,
+    progress: &JobProgress,
+) -> GpuResult<Vec<ImageBuffer>> {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Render Encoder"),
+    });
+    let output_count = pipelines.len();
+    let mut pending = Vec::with_capacity(output_count);
+
+    for (idx, (pipeline, output_config)) in pipelines
+        .iter()
+        .zip(shader_config.outputs.iter())
+        .enumerate()
+    {
+        progress.step(JobStage::Render, idx + 1, output_count)?;
 
-    for output_config in &shader_config.outputs {
-        let output_buffer = process_single_output(
+        let dither = dithering.for_output(&output_config.description);
+        let texture_size = output_sizes[idx];
+        let builtins_bind_group =
+            input_sizes.bind_group(device, builtins_layout, texture_size, idx as u32);
+        let output_texture = encode_output(
             device,
-            queue,
-            vertex_shader,
-            fragment_shader,
-            pipeline_layout,
-            output_config,
+            &mut encoder,
+            pipeline,
+            &output_config.description,
             texture_size,
+            dither.render_format(),
             texture_bind_group,
             params_bind_group,
-        )?;
-
-        output_buffers.push((output_buffer, output_config.description.clone()));
+            &builtins_bind_group,
+        );
+        pending.push(readback::copy_to_staging(
+            device,
+            staging,
+            &mut encoder,
+            &output_texture,
+            texture_size,
+            dither,
+            origin,
+        ));
     }
 
-    Ok(output_buffers)
+    progress.stage(JobStage::Readback)?;
+    readback::read_back(device, queue, staging, encoder, pending).await
 }
 
-/// Process a single output
-#[allow(clippy::too_many_arguments)]
-fn process_single_output(
+/// Create the render pipeline for a single output entry point
+pub fn create_output_pipeline(
     device: &wgpu::Device,
-    queue: &wgpu::Queue,
     vertex_shader: &wgpu::ShaderModule,
     fragment_shader: &wgpu::ShaderModule,
     pipeline_layout: &wgpu::PipelineLayout,
     output_config: &crate::components::texture_converter::OutputConfig,
-    texture_size: wgpu::Extent3d,
-    texture_bind_group: &wgpu::BindGroup,
-    params_bind_group: Option<&wgpu::BindGroup>,
-) -> GpuResult<ImageBuffer> {
-    // Create render pipeline for this output
-    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
         label: Some(&format!("Render Pipeline - {}", output_config.description)),
         layout: Some(pipeline_layout),
         vertex: wgpu::VertexState {
@@ -475,7 +896,7 @@ fn process_single_output(
             module: fragment_shader,
             entry_point: Some(&output_config.entry_point),
             targets: &[Some(wgpu::ColorTargetState {
-                format: wgpu::TextureFormat::Rgba8Unorm,
+                format,
                 blend: None,
                 write_mask: wgpu::ColorWrites::ALL,
             })],
@@ -489,30 +910,39 @@ fn process_single_output(
         multisample: wgpu::MultisampleState::default(),
         multiview: None,
         cache: None,
-    });
+    })
+}
 
+/// Record a render pass drawing an output pipeline into a new texture
+#[allow(clippy::too_many_arguments)]
+fn encode_output(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    pipeline: &wgpu::RenderPipeline,
+    description: &str,
+    texture_size: wgpu::Extent3d,
+    format: wgpu::TextureFormat,
+    texture_bind_group: &wgpu::BindGroup,
+    params_bind_group: &wgpu::BindGroup,
+    builtins_bind_group: &wgpu::BindGroup,
+) -> wgpu::Texture {
     // Create output texture
     let output_texture = device.create_texture(&wgpu::TextureDescriptor {
-        label: Some(&format!("Output Texture - {}", output_config.description)),
+        label: Some(&format!("Output Texture - {description}")),
         size: texture_size,
         mip_level_count: 1,
         sample_count: 1,
         dimension: wgpu::TextureDimension::D2,
-        format: wgpu::TextureFormat::Rgba8Unorm,
+        format,
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
         view_formats: &[],
     });
 
     let output_view = output_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-    // Render to texture
-    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-        label: Some(&format!("Render Encoder - {}", output_config.description)),
-    });
-
     {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some(&format!("Render Pass - {}", output_config.description)),
+            label: Some(&format!("Render Pass - {description}")),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                 view: &output_view,
                 resolve_target: None,
@@ -527,93 +957,12 @@ fn process_single_output(
             occlusion_query_set: None,
         });
 
-        render_pass.set_pipeline(&pipeline);
+        render_pass.set_pipeline(pipeline);
         render_pass.set_bind_group(0, texture_bind_group, &[]);
-        if let Some(params_bg) = params_bind_group {
-            render_pass.set_bind_group(1, params_bg, &[]);
-        }
+        render_pass.set_bind_group(1, params_bind_group, &[]);
+        render_pass.set_bind_group(BUILTINS_GROUP, builtins_bind_group, &[]);
         render_pass.draw(0..6, 0..1);
     }
 
-    // Copy texture to buffer for readback
-    let image_buffer =
-        copy_texture_to_buffer(device, queue, encoder, &output_texture, texture_size)?;
-
-    Ok(image_buffer)
-}
-
-/// Copy texture to buffer and read back to CPU
-///
-/// Copies GPU texture data to a staging buffer and reads it back.
-/// Handles row padding required by GPU buffer alignment.
-fn copy_texture_to_buffer(
-    device: &wgpu::Device,
-    queue: &wgpu::Queue,
-    mut encoder: wgpu::CommandEncoder,
-    texture: &wgpu::Texture,
-    texture_size: wgpu::Extent3d,
-) -> GpuResult<ImageBuffer> {
-    let width = texture_size.width;
-    let height = texture_size.height;
-    let bytes_per_row = 4 * width;
-    let padded_bytes_per_row = {
-        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
-        bytes_per_row.div_ceil(align) * align
-    };
-
-    let buffer_size = (padded_bytes_per_row * height) as wgpu::BufferAddress;
-    let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("Output Buffer"),
-        size: buffer_size,
-        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-        mapped_at_creation: false,
-    });
-
-    encoder.copy_texture_to_buffer(
-        texture.as_image_copy(),
-        wgpu::TexelCopyBufferInfo {
-            buffer: &output_buffer,
-            layout: wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(padded_bytes_per_row),
-                rows_per_image: Some(height),
-            },
-        },
-        texture_size,
-    );
-
-    let submission = queue.submit(std::iter::once(encoder.finish()));
-
-    // Read back the data
-    let buffer_slice = output_buffer.slice(..);
-    let (sender, receiver) = futures::channel::oneshot::channel();
-    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-        sender.send(result).unwrap();
-    });
-
-    let _ = device.poll(wgpu::PollType::WaitForSubmissionIndex(submission));
-
-    match pollster::block_on(receiver) {
-        Ok(Ok(())) => {}
-        Ok(Err(e)) => return Err(format!("Buffer mapping failed: {e}")),
-        Err(e) => return Err(format!("Channel error: {e}")),
-    }
-
-    let data = buffer_slice.get_mapped_range();
-    let mut rgba_data = vec![0u8; (width * height * 4) as usize];
-
-    // Remove padding from rows
-    for y in 0..height {
-        let src_offset = (y * padded_bytes_per_row) as usize;
-        let dst_offset = (y * bytes_per_row) as usize;
-        let row_bytes = bytes_per_row as usize;
-        rgba_data[dst_offset..dst_offset + row_bytes]
-            .copy_from_slice(&data[src_offset..src_offset + row_bytes]);
-    }
-
-    drop(data);
-    output_buffer.unmap();
-
-    ImageBuffer::from_raw(width, height, rgba_data)
-        .ok_or_else(|| "Failed to create ImageBuffer".to_string())
+    output_texture
 }
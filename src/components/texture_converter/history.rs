@@ -0,0 +1,83 @@
+//! History Module
+//!
+//! Undo/redo for edits to the texture converter state.
+//!
+//! Each undo step is a full snapshot of the editable state: the selected
+//! shader, every parameter value, the slot contents and the output selection.
+//! Images are held as shared references, so a snapshot only costs a few
+//! pointer copies no matter how large the loaded images are. Outputs are not
+//! stored; they are re-processed after a snapshot is restored.
+
+use crate::float_images::ExrLayers;
+use crate::porter_image::{ImageBuffer, PorterImage};
+use crate::preview::PreviewPyramid;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Maximum number of undo steps kept
+const MAX_HISTORY: usize = 100;
+
+/// Contents of one input slot
+#[derive(Debug, Clone)]
+pub struct SlotSnapshot {
+    pub image: Option<Arc<PorterImage>>,
+    pub preview: Option<PreviewPyramid>,
+    pub pixels: Option<Arc<ImageBuffer>>,
+    pub layers: Option<(PathBuf, ExrLayers)>,
+}
+
+/// Editable state at one point in time
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub selected_shader: Option<String>,
+    pub parameter_values: HashMap<String, HashMap<String, f32>>,
+    pub slots: Vec<SlotSnapshot>,
+    pub current_output_index: usize,
+}
+
+/// Undo and redo stacks
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+}
+
+impl History {
+    /// Record the state before an edit
+    ///
+    /// Starting a new edit discards anything that could be redone.
+    pub fn record(&mut self, snapshot: Snapshot) {
+        self.undo.push(snapshot);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Step back, returning the state to restore
+    ///
+    /// `current` is kept so the step can be redone.
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// Step forward again, returning the state to restore
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    /// Whether there is anything to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is anything to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
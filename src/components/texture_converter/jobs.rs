@@ -0,0 +1,229 @@
+//! Jobs Module
+//!
+//! Tracking, progress reporting and cancellation for background work.
+//!
+//! Processing, saving and batches each run as a job. While it runs, a job
+//! reports the stage it has reached (decode, upload, render, readback,
+//! encode) over the app's controller channel, and checks its cancel token at
+//! every stage boundary. Cancelling a job makes the next check fail, so the
+//! work stops there instead of running to completion; a GPU submission or
+//! file write already in progress finishes first.
+
+use crate::Controller;
+use crate::components::texture_converter::TextureSplitterMessage;
+use crate::messages::Message;
+use crate::windows::MainMessage;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Error returned by work that stopped because its job was cancelled
+pub const CANCELLED: &str = "Cancelled";
+
+/// What a job is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Process,
+    Save,
+    Batch,
+}
+
+/// Stage of work a job has reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStage {
+    Queued,
+    Decode,
+    Upload,
+    Render,
+    Readback,
+    Encode,
+}
+
+impl fmt::Display for JobStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            JobStage::Queued => "Queued",
+            JobStage::Decode => "Decoding",
+            JobStage::Upload => "Uploading",
+            JobStage::Render => "Rendering",
+            JobStage::Readback => "Reading back",
+            JobStage::Encode => "Encoding",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Shared flag that asks a job to stop
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Ask the job to stop at its next stage
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the job has been asked to stop
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Handle given to background work for reporting progress
+#[derive(Debug, Clone)]
+pub struct JobProgress {
+    id: u64,
+    controller: Controller,
+    cancel: CancelToken,
+}
+
+impl JobProgress {
+    /// Id of the job being reported
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Report that the job reached a stage
+    ///
+    /// Fails with [`CANCELLED`] if the job was cancelled, so callers can stop
+    /// with `?`.
+    pub fn stage(&self, stage: JobStage) -> Result<(), String> {
+        self.report(stage, None)
+    }
+
+    /// Report a stage along with progress through a number of items
+    pub fn step(&self, stage: JobStage, current: usize, total: usize) -> Result<(), String> {
+        self.report(stage, Some((current, total)))
+    }
+
+    /// Fail with [`CANCELLED`] if the job was cancelled, without reporting
+    pub fn check(&self) -> Result<(), String> {
+        if self.cancel.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn report(&self, stage: JobStage, step: Option<(usize, usize)>) -> Result<(), String> {
+        self.check()?;
+
+        self.controller
+            .send(Message::Main(MainMessage::TextureSplitter(
+                TextureSplitterMessage::JobProgressed(self.id, stage, step),
+            )));
+
+        Ok(())
+    }
+}
+
+/// A running job
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: u64,
+    pub kind: JobKind,
+    pub label: String,
+    pub stage: JobStage,
+    /// Progress through the items of the current stage, if it has several
+    pub step: Option<(usize, usize)>,
+    cancel: CancelToken,
+}
+
+impl Job {
+    /// Whether cancellation has been requested
+    pub fn is_cancelling(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+/// Jobs currently running
+#[derive(Debug, Default)]
+pub struct JobQueue {
+    jobs: Vec<Job>,
+    next_id: u64,
+}
+
+impl JobQueue {
+    /// Add a job, returning the handle its work reports through
+    pub fn start(
+        &mut self,
+        kind: JobKind,
+        label: impl Into<String>,
+        controller: &Controller,
+    ) -> JobProgress {
+        self.next_id += 1;
+        let cancel = CancelToken::default();
+
+        self.jobs.push(Job {
+            id: self.next_id,
+            kind,
+            label: label.into(),
+            stage: JobStage::Queued,
+            step: None,
+            cancel: cancel.clone(),
+        });
+
+        JobProgress {
+            id: self.next_id,
+            controller: controller.clone(),
+            cancel,
+        }
+    }
+
+    /// Record progress reported by a job
+    ///
+    /// A step carries over until a later report replaces it. Reports from
+    /// jobs that already finished are ignored.
+    pub fn progress(&mut self, id: u64, stage: JobStage, step: Option<(usize, usize)>) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.stage = stage;
+            if step.is_some() {
+                job.step = step;
+            }
+        }
+    }
+
+    /// Rename a job, e.g. to show the current batch item
+    pub fn set_label(&mut self, id: u64, label: impl Into<String>) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.label = label.into();
+            job.step = None;
+        }
+    }
+
+    /// Ask a job to stop; it stays listed until its work returns
+    pub fn cancel(&mut self, id: u64) {
+        if let Some(job) = self.jobs.iter().find(|job| job.id == id) {
+            job.cancel.cancel();
+        }
+    }
+
+    /// Whether a job has been asked to stop
+    pub fn is_cancelled(&self, id: u64) -> bool {
+        self.jobs
+            .iter()
+            .find(|job| job.id == id)
+            .is_some_and(Job::is_cancelling)
+    }
+
+    /// Remove a job whose work has returned
+    pub fn finish(&mut self, id: u64) {
+        self.jobs.retain(|job| job.id != id);
+    }
+
+    /// Cancel a job and stop tracking it, for work whose result is unwanted
+    pub fn abandon(&mut self, id: u64) {
+        self.cancel(id);
+        self.finish(id);
+    }
+
+    /// Jobs in the order they started
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    /// Whether any job of a kind is running
+    pub fn is_active(&self, kind: JobKind) -> bool {
+        self.jobs.iter().any(|job| job.kind == kind)
+    }
+}
@@ -2,40 +2,98 @@
 //!
 //! A modular texture processing component that uses GPU shaders to transform images.
 //! Organized into focused submodules for maintainability:
+//! - `alpha`: Straight and premultiplied alpha conversion
+//! - `batch`: Grouping and processing dropped folders of textures
+//! - `builtins`: Uniforms bound for every shader (sizes, output index, seed)
+//! - `channels`: Channel isolation for previews
+//! - `compare`: Before/after comparison between an input and the output
+//! - `displays`: Rendering preview display handles on the thread pool
+//! - `dither`: Ordered and blue-noise dithering of 8-bit outputs
+//! - `frames`: Frame-by-frame processing of cubemap and array inputs
+//! - `gpu_cache`: Shader pipelines and input textures kept between runs
 //! - `gpu_processor`: GPU shader execution and rendering
+//! - `history`: Undo/redo snapshots of the editable state
+//! - `jobs`: Progress reporting and cancellation for background work
+//! - `parallel`: Decoding and encoding on the shared thread pool
+//! - `readback`: Pooled staging buffers for reading outputs back from the GPU
+//! - `save_policy`: Conflict handling when saves would replace existing files
 //! - `shader_manager`: Shader loading and validation
+//! - `sizing`: Per-output scale, size and power-of-two rounding
+//! - `slot_matching`: Assigning dropped files to input slots by suffix
 //! - `state`: Component state management with caching
+//! - `stats`: Per-channel histograms and statistics
+//! - `tiling`: Tile planning for images beyond the GPU texture limit
 //! - `types`: Error types and type aliases
-
+//! - `viewport`: Shared zoom, pan and pixel inspection for previews
+
+mod alpha;
+mod batch;
+mod builtins;
+mod channels;
+mod compare;
+mod displays;
+mod dither;
+mod frames;
+mod gpu_cache;
 mod gpu_processor;
+mod history;
+mod jobs;
+mod parallel;
+mod readback;
+mod save_policy;
 mod shader_manager;
+mod sizing;
+mod slot_matching;
 mod state;
+mod stats;
+mod tiling;
 mod types;
+mod viewport;
 
 // Re-export public items
 pub use gpu_processor::process_images;
 pub use shader_manager::load_shaders;
 pub use state::TextureConverterState;
-pub use types::ImageFormat;
+pub use types::{ImageFormat, LoadedImage, ProcessedOutput};
 
 // Keep original types here for compatibility
+use crate::Controller;
 use crate::components::droppable_image_slot::DroppableImageSlot;
+use crate::float_images::{FloatRange, FloatRemap};
+use crate::ktx2_writer::{Ktx2Encoding, Ktx2Settings};
 use crate::messages::Message;
-use crate::porter_image::{ImageBuffer, PorterImage};
+use crate::porter_image::{ImageBuffer, PorterImage, SaveOptions};
 use crate::status::StatusMessage;
+use alpha::{AlphaMode, AlphaPreview};
+use batch::{BatchQueue, BatchRun, GroupOutput};
+use channels::ChannelView;
+use compare::{CompareMode, CompareSource};
+use displays::{
+    CompareRequest, DisplayRequest, DisplayScope, DisplaySource, RenderedCompare, RenderedDisplays,
+};
+use dither::{DitherMode, DitherOverride, Dithering};
+use frames::{FrameChoice, FrameSource, FramedOutput};
 use iced::widget::{button, column, container, pick_list, row, text};
 use iced::{Element, Length, Task};
+use jobs::{JobKind, JobProgress, JobStage};
+use save_policy::SavePolicy;
 use serde::{Deserialize, Serialize};
+use sizing::{OutputSizes, SizeOverride};
+use stats::{StatsReport, StatsSource};
 use std::path::PathBuf;
 use std::sync::Arc;
+use types::{RenderOptions, SaveFormats};
+use viewport::PreviewTarget;
 
 // Constants for UI sizing and timing
 /// Debounce delay for parameter changes (milliseconds)
 const PARAMETER_DEBOUNCE_MS: u64 = 150;
 
-/// Maximum image dimension supported
-#[allow(dead_code)]
-const MAX_IMAGE_DIMENSION: u32 = 8192;
+/// Longest side of outputs rendered while a slider is being dragged
+const PREVIEW_MAX_DIMENSION: u32 = 1024;
+
+/// Default apron around tiles when an image exceeds the GPU texture limit
+const DEFAULT_TILE_APRON: u32 = 4;
 
 /// Size for single input slot preview
 const SLOT_SIZE_SINGLE: f32 = 280.0;
@@ -47,6 +105,8 @@ const SLOT_SIZE_THREE: f32 = 220.0;
 const SLOT_SIZE_FOUR: f32 = 200.0;
 /// Size for five or more input slots preview
 const SLOT_SIZE_MANY: f32 = 180.0;
+/// Size for the output preview
+const OUTPUT_PREVIEW_SIZE: f32 = 400.0;
 
 // Shared vertex shader for all texture processing operations
 pub const FULLSCREEN_QUAD_VERTEX_SHADER: &[u8] =
@@ -56,6 +116,16 @@ pub const FULLSCREEN_QUAD_VERTEX_SHADER: &[u8] =
 pub struct TextureSplitter {
     state: TextureConverterState,
     selected_format: ImageFormat,
+    save_policy: SavePolicy,
+    ktx2_settings: Ktx2Settings,
+    /// Dithering for outputs without an override
+    dither_mode: DitherMode,
+    /// Round every output to a power of two
+    power_of_two_sizes: bool,
+    /// How float inputs outside 0-1 are read
+    float_range: FloatRange,
+    /// Channel that background jobs report progress through
+    controller: Controller,
 }
 
 /// Messages produced by the texture splitter component
@@ -65,17 +135,60 @@ pub enum TextureSplitterMessage {
     ShadersLoaded(Result<(Vec<ShaderConfig>, usize), String>),
     ParameterChanged(String, f32),  // (parameter_name, value)
     DebouncedParameterProcess(u64), // Process parameters after debounce (generation)
+    ParameterReleased,              // Slider released, render at full resolution
     BrowseInput(usize),             // Browse for input slot at index
     InputFileSelected(usize, Option<PathBuf>), // Input slot index, path
-    InputImageLoaded(usize, Result<PorterImage, String>), // Input slot index, image
-    MergeCompleted(Result<Vec<(ImageBuffer, String)>, String>, u64), // Result (outputs with descriptions), generation
+    InputImageLoaded(usize, Result<LoadedImage, String>), // Input slot index, image
+    MaterialLoaded(Vec<(usize, Result<LoadedImage, String>)>), // Dropped file and siblings, by slot
+    InputLayerSelected(usize, String), // Input slot index, EXR layer name
+    MergeCompleted(Result<Vec<ProcessedOutput>, String>, u64), // Result (outputs with previews), generation
     SaveAllPressed,
     FormatSelected(ImageFormat),
+    SavePolicySelected(SavePolicy),
     ClearPressed,
     AllImagesSaved(Result<Vec<PathBuf>, String>),
     NextOutput,
     PreviousOutput,
     ReloadShaders,
+    PreviewHovered(PreviewTarget, iced::Point), // Cursor moved over a preview
+    PreviewExited,
+    PreviewPressed,
+    PreviewReleased,
+    PreviewScrolled(iced::mouse::ScrollDelta),
+    ResetZoom,
+    ChannelSelected(PreviewTarget, ChannelView), // Channel shown by one preview
+    AlphaPreviewSelected(AlphaPreview),          // Alpha interpretation shown by every preview
+    CompareModeSelected(CompareMode),
+    CompareInputSelected(CompareSource),
+    FlickerTick(u64), // Swap flicker sides (generation)
+    StatsSourceSelected(StatsSource),
+    DisplaysRendered(Box<RenderedDisplays>),
+    StatsComputed(u64, Box<StatsReport>), // Generation, statistics for the selected preview
+    Undo,
+    Redo,
+    BrowseFocusedSlot,
+    SaveCurrentPressed,
+    CurrentImageSaved(Result<PathBuf, String>),
+    BatchGroupToggled(usize, bool), // Group index, selected
+    BatchProcessPressed,
+    BatchFolderSelected(Option<(PathBuf, SavePolicy)>), // Output folder, resolved save policy
+    BatchGroupProcessed(usize, Result<GroupOutput, String>), // Group index, saved paths
+    BatchFilesFound(Vec<PathBuf>),                      // Image files found in dropped folders
+    BatchClosed,
+    OutputSaveToggled(String, bool), // Output description, included in Save All
+    OutputFormatSelected(String, ImageFormat), // Output description, format it is saved in
+    Ktx2EncodingSelected(Ktx2Encoding),
+    Ktx2ZstdToggled(bool),
+    Ktx2SrgbToggled(bool),
+    DitherSelected(DitherMode),
+    OutputDitherSelected(String, DitherOverride), // Output description, dithering it is rendered with
+    OutputSizeSelected(String, SizeOverride),     // Output description, size it is rendered at
+    PowerOfTwoToggled(bool),
+    FloatRangeSelected(FloatRange),
+    FrameSelected(FrameChoice),
+    SaveMipmapsToggled(bool),
+    JobProgressed(u64, JobStage, Option<(usize, usize)>), // Job id, stage, step of steps
+    CancelJob(u64),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +211,9 @@ pub struct ShaderMetadata {
     pub author: String,
     #[serde(default)]
     pub version: String,
+    /// Extra texels rendered around each tile when processing oversized images
+    #[serde(default = "default_tile_apron")]
+    pub tile_apron: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,6 +222,12 @@ pub struct InputConfig {
     pub description: String,
     #[serde(default = "default_true")]
     pub required: bool,
+    /// Other suffixes accepted when matching dropped files to this slot
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// How the input file stores alpha; converted to straight on upload
+    #[serde(default)]
+    pub alpha_mode: AlphaMode,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +237,21 @@ pub struct OutputConfig {
     pub description: String,
     #[serde(default = "default_format")]
     pub format: String,
+    /// How saved files store alpha; converted from straight before saving
+    #[serde(default)]
+    pub alpha_mode: AlphaMode,
+    /// Size relative to the first input, used when no width or height is set
+    #[serde(default)]
+    pub scale: Option<f32>,
+    /// Absolute width; with no height, the height keeps the aspect ratio
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Absolute height; with no width, the width keeps the aspect ratio
+    #[serde(default)]
+    pub height: Option<u32>,
+    /// Round the width and height to the nearest power of two
+    #[serde(default)]
+    pub power_of_two: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,6 +273,10 @@ fn default_format() -> String {
     "Rgba8Unorm".to_string()
 }
 
+fn default_tile_apron() -> u32 {
+    DEFAULT_TILE_APRON
+}
+
 impl std::fmt::Display for ShaderConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.shader.name)
@@ -150,15 +291,45 @@ impl PartialEq for ShaderConfig {
 
 impl Eq for ShaderConfig {}
 
+impl ShaderConfig {
+    /// Alpha mode of the input at a slot
+    pub fn input_alpha_mode(&self, slot_idx: usize) -> AlphaMode {
+        self.inputs
+            .get(slot_idx)
+            .map(|input| input.alpha_mode)
+            .unwrap_or_default()
+    }
+
+    /// Alpha mode an output is saved with
+    pub fn output_alpha_mode(&self, description: &str) -> AlphaMode {
+        self.outputs
+            .iter()
+            .find(|output| output.description == description)
+            .map(|output| output.alpha_mode)
+            .unwrap_or_default()
+    }
+}
+
 impl TextureSplitter {
     /// Creates a new texture splitter component
     pub fn new() -> Self {
         Self {
             state: TextureConverterState::new(),
             selected_format: ImageFormat::default(),
+            save_policy: SavePolicy::default(),
+            ktx2_settings: Ktx2Settings::default(),
+            dither_mode: DitherMode::default(),
+            power_of_two_sizes: false,
+            float_range: FloatRange::default(),
+            controller: Controller::new(),
         }
     }
 
+    /// Set the channel background jobs report progress through
+    pub fn set_controller(&mut self, controller: Controller) {
+        self.controller = controller;
+    }
+
     /// Initialize the texture splitter by loading shaders
     pub fn initialize() -> Task<Message> {
         Task::perform(load_shaders(), |result| {
@@ -168,12 +339,27 @@ impl TextureSplitter {
         })
     }
 
+    /// Names of all loaded shaders, for the command palette
+    pub fn shader_names(&self) -> Vec<String> {
+        self.state
+            .shaders
+            .iter()
+            .map(|shader| shader.shader.name.clone())
+            .collect()
+    }
+
     /// Update the texture splitter state based on messages
     pub fn update(&mut self, message: TextureSplitterMessage) -> Task<Message> {
         match message {
             TextureSplitterMessage::ShaderSelected(name) => self.on_shader_selected(name),
             TextureSplitterMessage::ShadersLoaded(result) => self.on_shaders_loaded(result),
             TextureSplitterMessage::ParameterChanged(param_name, value) => {
+                if self.state.selected_shader.is_some() && !self.state.parameter_edit_pending {
+                    // Record once per burst of slider movement
+                    self.record_history();
+                    self.state.parameter_edit_pending = true;
+                }
+
                 if let Some(shader_name) = &self.state.selected_shader {
                     if let Some(param_map) = self.state.parameter_values.get_mut(shader_name) {
                         param_map.insert(param_name, value);
@@ -182,8 +368,17 @@ impl TextureSplitter {
                     self.state.parameter_debounce_generation += 1;
                     let generation = self.state.parameter_debounce_generation;
 
-                    // Wait before processing (debounce)
-                    return Task::perform(
+                    // Show a low-resolution result straight away, one at a time
+                    let preview = if self.state.process_job.is_some() && self.state.merge_is_preview
+                    {
+                        self.state.preview_stale = true;
+                        Task::none()
+                    } else {
+                        self.trigger_merge(true)
+                    };
+
+                    // Wait before processing at full resolution (debounce)
+                    let debounce = Task::perform(
                         async move {
                             futures_timer::Delay::new(std::time::Duration::from_millis(
                                 PARAMETER_DEBOUNCE_MS,
@@ -197,25 +392,41 @@ impl TextureSplitter {
                             ))
                         },
                     );
+                    return Task::batch([preview, debounce]);
                 }
                 Task::none()
             }
             TextureSplitterMessage::DebouncedParameterProcess(generation) => {
                 // Only process if this is still the latest parameter change
-                if generation == self.state.parameter_debounce_generation
-                    && self.state.all_required_slots_filled()
-                {
-                    return self.trigger_merge_from_slots();
+                if generation != self.state.parameter_debounce_generation {
+                    return Task::none();
                 }
-                Task::none()
+
+                self.on_parameters_settled()
+            }
+            TextureSplitterMessage::ParameterReleased => {
+                if !self.state.parameter_edit_pending {
+                    return Task::none();
+                }
+
+                // Skip the rest of the debounce and render in full now
+                self.state.parameter_debounce_generation += 1;
+                self.on_parameters_settled()
+            }
+            TextureSplitterMessage::BrowseInput(slot_idx) => {
+                self.state.focused_slot = Some(slot_idx);
+                self.on_browse_input(slot_idx)
             }
-            TextureSplitterMessage::BrowseInput(slot_idx) => self.on_browse_input(slot_idx),
             TextureSplitterMessage::InputFileSelected(slot_idx, path_opt) => {
                 self.on_input_file_selected(slot_idx, path_opt)
             }
             TextureSplitterMessage::InputImageLoaded(slot_idx, result) => {
                 self.on_input_image_loaded(slot_idx, result)
             }
+            TextureSplitterMessage::MaterialLoaded(loaded) => self.on_material_loaded(loaded),
+            TextureSplitterMessage::InputLayerSelected(slot_idx, layer) => {
+                self.on_input_layer_selected(slot_idx, layer)
+            }
             TextureSplitterMessage::MergeCompleted(result, generation) => {
                 self.on_merge_completed(result, generation)
             }
@@ -224,15 +435,21 @@ impl TextureSplitter {
                 self.selected_format = format;
                 Task::none()
             }
+            TextureSplitterMessage::SavePolicySelected(policy) => {
+                self.save_policy = policy;
+                Task::none()
+            }
             TextureSplitterMessage::ClearPressed => self.on_clear(),
             TextureSplitterMessage::AllImagesSaved(result) => self.on_all_images_saved(result),
             TextureSplitterMessage::NextOutput => {
                 self.state.next_output();
-                Task::none()
+                let displays = self.refresh_displays();
+                Task::batch([displays, self.refresh_stats_for(PreviewTarget::Output)])
             }
             TextureSplitterMessage::PreviousOutput => {
                 self.state.previous_output();
-                Task::none()
+                let displays = self.refresh_displays();
+                Task::batch([displays, self.refresh_stats_for(PreviewTarget::Output)])
             }
             TextureSplitterMessage::ReloadShaders => {
                 self.state.shaders_loading = true;
@@ -243,6 +460,189 @@ impl TextureSplitter {
                     ))
                 })
             }
+            TextureSplitterMessage::PreviewHovered(target, point) => {
+                self.on_preview_hovered(target, point)
+            }
+            TextureSplitterMessage::PreviewExited => {
+                self.state.hovered_preview = None;
+                self.state.dragging_preview = false;
+                self.state.dragging_split = false;
+                Task::none()
+            }
+            TextureSplitterMessage::PreviewPressed => {
+                if self.is_over_split_divider() {
+                    self.state.dragging_split = true;
+                } else {
+                    self.state.dragging_preview = true;
+                }
+                Task::none()
+            }
+            TextureSplitterMessage::PreviewReleased => {
+                self.state.dragging_preview = false;
+                self.state.dragging_split = false;
+                Task::none()
+            }
+            TextureSplitterMessage::PreviewScrolled(delta) => self.on_preview_scrolled(delta),
+            TextureSplitterMessage::ResetZoom => {
+                self.state.view = viewport::ViewTransform::default();
+                self.refresh_displays()
+            }
+            TextureSplitterMessage::ChannelSelected(target, channel) => {
+                self.state.set_channel_view(target, channel);
+                self.refresh_displays()
+            }
+            TextureSplitterMessage::AlphaPreviewSelected(preview) => {
+                self.state.preview_alpha = preview;
+                self.refresh_displays()
+            }
+            TextureSplitterMessage::CompareModeSelected(mode) => {
+                self.on_compare_mode_selected(mode)
+            }
+            TextureSplitterMessage::CompareInputSelected(source) => {
+                self.state.compare_input = source.index;
+                self.refresh_displays()
+            }
+            TextureSplitterMessage::FlickerTick(generation) => {
+                if generation != self.state.flicker_generation
+                    || self.state.compare_mode != CompareMode::Flicker
+                {
+                    return Task::none();
+                }
+
+                self.state.flicker_showing_input = !self.state.flicker_showing_input;
+                schedule_flicker_tick(generation)
+            }
+            TextureSplitterMessage::StatsSourceSelected(source) => {
+                self.state.stats_target = Some(source.target);
+                self.refresh_stats()
+            }
+            TextureSplitterMessage::DisplaysRendered(rendered) => {
+                self.on_displays_rendered(*rendered)
+            }
+            TextureSplitterMessage::StatsComputed(generation, report) => {
+                if generation == self.state.stats_generation {
+                    self.state.stats = Some(*report);
+                }
+                Task::none()
+            }
+            TextureSplitterMessage::Undo => self.on_undo(),
+            TextureSplitterMessage::Redo => self.on_redo(),
+            TextureSplitterMessage::BrowseFocusedSlot => match self.focused_slot() {
+                Some(slot_idx) => self.on_browse_input(slot_idx),
+                None => Task::none(),
+            },
+            TextureSplitterMessage::SaveCurrentPressed => self.on_save_current(),
+            TextureSplitterMessage::BatchGroupToggled(idx, selected) => {
+                if let Some(group) = self
+                    .state
+                    .batch
+                    .as_mut()
+                    .filter(|queue| !queue.is_running())
+                    .and_then(|queue| queue.groups.get_mut(idx))
+                {
+                    group.selected = selected;
+                }
+                Task::none()
+            }
+            TextureSplitterMessage::BatchProcessPressed => self.on_batch_process(),
+            TextureSplitterMessage::BatchFolderSelected(selection) => {
+                self.on_batch_folder_selected(selection)
+            }
+            TextureSplitterMessage::BatchGroupProcessed(idx, result) => {
+                self.on_batch_group_processed(idx, result)
+            }
+            TextureSplitterMessage::BatchFilesFound(files) => self.on_batch_files_found(files),
+            TextureSplitterMessage::BatchClosed => {
+                if !self
+                    .state
+                    .batch
+                    .as_ref()
+                    .is_some_and(BatchQueue::is_running)
+                {
+                    self.state.batch = None;
+                }
+                Task::none()
+            }
+            TextureSplitterMessage::OutputSaveToggled(description, included) => {
+                if included {
+                    self.state.skipped_outputs.remove(&description);
+                } else {
+                    self.state.skipped_outputs.insert(description);
+                }
+                Task::none()
+            }
+            TextureSplitterMessage::OutputFormatSelected(description, format) => {
+                if format == self.selected_format {
+                    self.state.output_formats.remove(&description);
+                } else {
+                    self.state.output_formats.insert(description, format);
+                }
+                Task::none()
+            }
+            TextureSplitterMessage::Ktx2EncodingSelected(encoding) => {
+                self.ktx2_settings.encoding = encoding;
+                Task::none()
+            }
+            TextureSplitterMessage::Ktx2ZstdToggled(zstd) => {
+                self.ktx2_settings.zstd = zstd;
+                Task::none()
+            }
+            TextureSplitterMessage::Ktx2SrgbToggled(srgb) => {
+                self.ktx2_settings.srgb = srgb;
+                Task::none()
+            }
+            TextureSplitterMessage::DitherSelected(mode) => {
+                self.dither_mode = mode;
+                self.rerender_outputs()
+            }
+            TextureSplitterMessage::OutputDitherSelected(description, choice) => {
+                match choice {
+                    DitherOverride::Default => {
+                        self.state.output_dithers.remove(&description);
+                    }
+                    DitherOverride::Mode(mode) => {
+                        self.state.output_dithers.insert(description, mode);
+                    }
+                }
+                self.rerender_outputs()
+            }
+            TextureSplitterMessage::OutputSizeSelected(description, size) => {
+                if size == SizeOverride::Configured {
+                    self.state.output_sizes.remove(&description);
+                } else {
+                    self.state.output_sizes.insert(description, size);
+                }
+                self.rerender_outputs()
+            }
+            TextureSplitterMessage::PowerOfTwoToggled(enabled) => {
+                self.power_of_two_sizes = enabled;
+                self.rerender_outputs()
+            }
+            TextureSplitterMessage::FloatRangeSelected(range) => {
+                self.float_range = range;
+                self.state.status =
+                    StatusMessage::info("Float range applies to EXR and HDR files loaded next");
+                Task::none()
+            }
+            TextureSplitterMessage::FrameSelected(choice) => {
+                self.state.select_frame(choice.index);
+                self.trigger_merge_from_slots()
+            }
+            TextureSplitterMessage::SaveMipmapsToggled(mipmaps) => {
+                self.state.save_mipmaps = Some(mipmaps);
+                Task::none()
+            }
+            TextureSplitterMessage::JobProgressed(id, stage, step) => {
+                self.state.jobs.progress(id, stage, step);
+                Task::none()
+            }
+            TextureSplitterMessage::CancelJob(id) => {
+                self.state.jobs.cancel(id);
+                Task::none()
+            }
+            TextureSplitterMessage::CurrentImageSaved(result) => {
+                self.on_current_image_saved(result)
+            }
         }
     }
 
@@ -308,7 +708,7 @@ impl TextureSplitter {
 
         // Output preview with navigation
         let output_widget = if !self.state.outputs.is_empty() {
-            let current_handle = &self.state.outputs[self.state.current_output_index];
+            let current_preview = &self.state.outputs[self.state.current_output_index];
             let current_desc = &self.state.output_descriptions[self.state.current_output_index];
 
             // Create navigation info text
@@ -346,15 +746,20 @@ impl TextureSplitter {
                 .style(crate::widget_helpers::primary_button_style);
 
             // Build preview with navigation
-            let preview = container(
-                iced::widget::image(current_handle.clone())
-                    .content_fit(iced::ContentFit::Contain)
-                    .width(400)
-                    .height(400),
-            )
-            .width(400)
-            .height(400)
-            .center_x(Length::Fill);
+            let preview_content = if self.state.is_comparing() {
+                self.view_compare(current_preview)
+            } else {
+                self.view_zoomable(
+                    PreviewTarget::Output,
+                    current_preview.handle_for_size(OUTPUT_PREVIEW_SIZE),
+                    OUTPUT_PREVIEW_SIZE,
+                )
+            };
+
+            let preview = container(preview_content)
+                .width(OUTPUT_PREVIEW_SIZE)
+                .height(OUTPUT_PREVIEW_SIZE)
+                .center_x(Length::Fill);
 
             if self.state.outputs.len() > 1 {
                 column![
@@ -402,11 +807,14 @@ impl TextureSplitter {
                     move |val| {
                         TextureSplitterMessage::ParameterChanged(param_name.clone(), val as f32)
                     },
+                    TextureSplitterMessage::ParameterReleased,
                 );
                 controls.push(param_slider);
             }
         }
 
+        controls.push(self.view_stats_panel());
+
         // Create action buttons with format selector (always visible)
         let mut buttons = Vec::new();
 
@@ -420,56 +828,353 @@ impl TextureSplitter {
         .placeholder("Format")
         .style(pick_list_style);
 
-        let save_all_button = create_save_all_button(
-            self.state.is_saving,
-            !self.state.output_buffers.is_empty(),
-            TextureSplitterMessage::SaveAllPressed,
-        );
+        let is_saving = self.state.jobs.is_active(JobKind::Save);
+        let can_save = !self.state.output_buffers.is_empty() && !self.state.outputs_are_preview;
+        let save_all_button =
+            create_save_all_button(is_saving, can_save, TextureSplitterMessage::SaveAllPressed);
+
+        let save_current_button = button("Save This Output")
+            .on_press_maybe(
+                (!is_saving && can_save).then_some(TextureSplitterMessage::SaveCurrentPressed),
+            )
+            .padding(12)
+            .width(Length::Fill)
+            .style(crate::widget_helpers::secondary_button_style);
+
+        let policy_selector = pick_list(
+            &SavePolicy::ALL[..],
+            Some(self.save_policy),
+            TextureSplitterMessage::SavePolicySelected,
+        )
+        .padding(12)
+        .placeholder("If file exists")
+        .style(pick_list_style);
 
         let save_row = row![
             container(format_selector).width(Length::FillPortion(1)),
-            container(save_all_button).width(Length::FillPortion(2)),
+            container(policy_selector).width(Length::FillPortion(1)),
+            container(save_all_button).width(Length::FillPortion(1)),
+            container(save_current_button).width(Length::FillPortion(1)),
         ]
         .spacing(10)
         .width(Length::Fill);
 
         buttons.push(save_row.into());
 
+        if let Some(jobs) = self.view_jobs() {
+            buttons.push(jobs);
+        }
+
+        // Choose which outputs Save All writes
+        if self.state.output_descriptions.len() > 1 {
+            let mut output_toggles = row![text("Save:").size(12)]
+                .spacing(12)
+                .align_y(iced::Alignment::Center);
+
+            let formats = self.save_formats();
+            for description in &self.state.output_descriptions {
+                let included = !self.state.skipped_outputs.contains(description);
+                let output_format = pick_list(
+                    &ImageFormat::ALL[..],
+                    Some(formats.for_output(description)),
+                    |format| {
+                        TextureSplitterMessage::OutputFormatSelected(description.clone(), format)
+                    },
+                )
+                .text_size(12)
+                .padding([2, 6])
+                .style(pick_list_style);
+                let dither_choice = self
+                    .state
+                    .output_dithers
+                    .get(description)
+                    .map_or(DitherOverride::Default, |&mode| DitherOverride::Mode(mode));
+                let output_dither =
+                    pick_list(&DitherOverride::ALL[..], Some(dither_choice), |choice| {
+                        TextureSplitterMessage::OutputDitherSelected(description.clone(), choice)
+                    })
+                    .text_size(12)
+                    .padding([2, 6])
+                    .style(pick_list_style);
+
+                output_toggles = output_toggles.push(
+                    row![
+                        iced::widget::checkbox(description.as_str(), included)
+                            .on_toggle(|included| {
+                                TextureSplitterMessage::OutputSaveToggled(
+                                    description.clone(),
+                                    included,
+                                )
+                            })
+                            .size(14)
+                            .text_size(12),
+                        output_format,
+                        output_dither,
+                    ]
+                    .spacing(6)
+                    .align_y(iced::Alignment::Center),
+                );
+            }
+
+            buttons.push(output_toggles.wrap().into());
+        }
+
+        if let Some(ktx2_row) = self.view_ktx2_settings() {
+            buttons.push(ktx2_row);
+        }
+
+        buttons.push(self.view_dither_settings());
+
+        if let Some(size_row) = self.view_size_settings() {
+            buttons.push(size_row);
+        }
+
+        let undo_button = button("Undo")
+            .on_press_maybe(
+                self.state
+                    .history
+                    .can_undo()
+                    .then_some(TextureSplitterMessage::Undo),
+            )
+            .padding(12)
+            .style(crate::widget_helpers::secondary_button_style);
+        let redo_button = button("Redo")
+            .on_press_maybe(
+                self.state
+                    .history
+                    .can_redo()
+                    .then_some(TextureSplitterMessage::Redo),
+            )
+            .padding(12)
+            .style(crate::widget_helpers::secondary_button_style);
         let clear_button = create_clear_button(TextureSplitterMessage::ClearPressed);
-        buttons.push(clear_button);
+
+        buttons.push(
+            row![
+                undo_button,
+                redo_button,
+                container(clear_button).width(Length::Fill)
+            ]
+            .spacing(10)
+            .width(Length::Fill)
+            .into(),
+        );
 
         // Build main layout using baker_layout
         let baker_content = create_baker_layout(BakerLayoutConfig {
             input_slots: input_slot_views,
             output_widget,
+            inspector: Some(self.view_inspector()),
             controls,
             buttons,
             status_bar: status_bar.into(),
         });
 
+        // A dropped batch takes the place of the single material layout
+        let main_content = match &self.state.batch {
+            Some(queue) => self.view_batch(queue),
+            None => baker_content,
+        };
+
         // Combine shader picker at top with baker layout below
-        column![shader_picker_section, baker_content]
+        column![shader_picker_section, main_content]
             .width(Length::Fill)
             .height(Length::Fill)
             .into()
     }
 
+    /// Handle files and folders dropped onto the window
+    ///
+    /// A single file is loaded into the matching slot. Folders and multi-file
+    /// drops are grouped into materials for the selected shader and shown as
+    /// a batch queue.
+    pub fn on_paths_dropped(&mut self, paths: Vec<PathBuf>) -> Task<Message> {
+        if self
+            .state
+            .batch
+            .as_ref()
+            .is_some_and(BatchQueue::is_running)
+        {
+            self.state.status = StatusMessage::warning("Wait for the current batch to finish.");
+            return Task::none();
+        }
+
+        // A single file is a normal drop onto the slots
+        if let Some(path) = single_file(&paths) {
+            return self.on_file_dropped(path);
+        }
+
+        if self.state.get_selected_shader().is_none() {
+            self.state.status = StatusMessage::warning("Select a shader before dropping a batch.");
+            return Task::none();
+        }
+
+        // Searching folders touches every entry, so it runs off the UI thread
+        self.state.status = StatusMessage::info("Searching dropped folders...");
+        Task::perform(
+            async move {
+                parallel::spawn(move || batch::collect_image_files(&paths))
+                    .await
+                    .unwrap_or_default()
+            },
+            |files| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::BatchFilesFound(files),
+                ))
+            },
+        )
+    }
+
+    /// Handle the image files of a dropped batch being found
+    ///
+    /// Groups them into materials for the selected shader.
+    fn on_batch_files_found(&mut self, files: Vec<PathBuf>) -> Task<Message> {
+        let Some(shader) = self.state.get_selected_shader() else {
+            return Task::none();
+        };
+        if self
+            .state
+            .batch
+            .as_ref()
+            .is_some_and(BatchQueue::is_running)
+        {
+            return Task::none();
+        }
+
+        let queue = BatchQueue::from_files(&shader.inputs, files);
+        if queue.groups.is_empty() {
+            self.state.status = StatusMessage::warning(format!(
+                "No images matching the inputs of {} were found.",
+                shader.shader.name
+            ));
+            return Task::none();
+        }
+
+        self.state.status = StatusMessage::info(format!(
+            "Found {} material{}. Choose which to process.",
+            queue.groups.len(),
+            if queue.groups.len() == 1 { "" } else { "s" }
+        ));
+        self.state.batch = Some(queue);
+        Task::none()
+    }
+
     /// Handle file dropped onto the window
     ///
-    /// Loads the dropped file into the first available empty slot.
-    /// If all slots are filled, displays a warning message.
-    pub fn on_file_dropped(&mut self, path: PathBuf) -> Task<Message> {
-        // Load into the first empty slot
-        for (idx, slot) in self.state.input_slots.iter().enumerate() {
-            if slot.image.is_none() {
-                return self.on_input_file_selected(idx, Some(path));
+    /// Loads the dropped file into the slot whose suffix it matches and fills
+    /// the other empty slots with siblings from the same folder. Files that
+    /// match no suffix go into the first empty slot, with a warning if all
+    /// slots are filled.
+    fn on_file_dropped(&mut self, path: PathBuf) -> Task<Message> {
+        let inputs = self
+            .state
+            .get_selected_shader()
+            .map(|shader| shader.inputs)
+            .unwrap_or_default();
+
+        let Some(found) = slot_matching::match_slot(&inputs, &path) else {
+            // No suffix matched, so load into the first empty slot
+            return match self
+                .state
+                .input_slots
+                .iter()
+                .position(|slot| slot.image.is_none())
+            {
+                Some(idx) => self.on_input_file_selected(idx, Some(path)),
+                None => {
+                    self.state.status = StatusMessage::warning(
+                        "All input slots are filled. Clear to load new images.",
+                    );
+                    Task::none()
+                }
+            };
+        };
+
+        // Pull in siblings for the other empty slots
+        let empty_slots: Vec<usize> = self
+            .state
+            .input_slots
+            .iter()
+            .enumerate()
+            .filter(|(idx, slot)| *idx != found.slot && slot.image.is_none())
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.state.status = StatusMessage::info("Loading matching images...");
+
+        // Scan the folder and decode off the UI thread
+        let dropped_slot = found.slot;
+        let float_range = self.float_range;
+        Task::perform(
+            async move {
+                parallel::spawn(move || {
+                    let siblings =
+                        slot_matching::find_siblings(&inputs, &path, &found.base, &empty_slots);
+                    let files = std::iter::once((found.slot, path))
+                        .chain(siblings)
+                        .collect();
+                    parallel::load_slot_images(files, float_range)
+                })
+                .await
+                .unwrap_or_else(|e| vec![(dropped_slot, Err(e))])
+            },
+            |loaded| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::MaterialLoaded(loaded),
+                ))
+            },
+        )
+    }
+
+    /// Handle a dropped file and its siblings finishing loading
+    ///
+    /// Every loaded image goes into its slot as one undo step, followed by a
+    /// single merge.
+    fn on_material_loaded(
+        &mut self,
+        loaded: Vec<(usize, Result<LoadedImage, String>)>,
+    ) -> Task<Message> {
+        let mut loaded_slots = Vec::new();
+        let mut errors = Vec::new();
+        let mut remapped = Vec::new();
+
+        for (slot_idx, result) in loaded {
+            match result {
+                Ok(image) if slot_idx < self.state.input_slots.len() => {
+                    if loaded_slots.is_empty() {
+                        self.record_history();
+                    }
+                    if let Some(remap) = self.load_into_slot(slot_idx, image) {
+                        remapped.push(remap_warning(slot_idx, &remap));
+                    }
+                    loaded_slots.push(slot_idx);
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(e),
             }
         }
 
-        // If all slots are filled, show a message
-        self.state.status =
-            StatusMessage::warning("All input slots are filled. Clear to load new images.");
-        Task::none()
+        self.state.status = match (errors.first(), loaded_slots.len()) {
+            (Some(e), _) => StatusMessage::error(e.clone()),
+            _ if !remapped.is_empty() => StatusMessage::warning(remapped.join("; ")),
+            (None, 1) => {
+                StatusMessage::success(format!("Loaded image for slot {}", loaded_slots[0]))
+            }
+            (None, count) => StatusMessage::success(format!("Loaded {count} matching images")),
+        };
+
+        let Some(&first_slot) = loaded_slots.first() else {
+            return Task::none();
+        };
+
+        let displays = self.refresh_displays();
+        let stats_task = self.refresh_stats_for(PreviewTarget::Input(first_slot));
+
+        if self.state.all_required_slots_filled() {
+            return Task::batch([displays, stats_task, self.trigger_merge_from_slots()]);
+        }
+
+        Task::batch([displays, stats_task])
     }
 
     /// Handle shaders loaded result
@@ -530,6 +1235,16 @@ impl TextureSplitter {
     /// Initializes parameter values and input slots for the selected shader.
     /// Clears existing outputs when switching shaders.
     fn on_shader_selected(&mut self, name: String) -> Task<Message> {
+        // Switching shaders is undoable, the initial selection isn't
+        if self
+            .state
+            .selected_shader
+            .as_ref()
+            .is_some_and(|current| *current != name)
+        {
+            self.record_history();
+        }
+
         self.state.selected_shader = Some(name.clone());
 
         // Initialize parameter values and input slots
@@ -539,6 +1254,20 @@ impl TextureSplitter {
 
             // Clear outputs when switching shaders
             self.state.clear_outputs();
+            self.state.skipped_outputs.clear();
+            self.state.output_formats.clear();
+            self.state.output_dithers.clear();
+            self.state.output_sizes.clear();
+
+            // Match a waiting batch against the new inputs
+            if let Some(queue) = self
+                .state
+                .batch
+                .as_mut()
+                .filter(|queue| !queue.is_running())
+            {
+                queue.regroup(&shader.inputs);
+            }
         }
 
         Task::none()
@@ -551,7 +1280,13 @@ impl TextureSplitter {
         Task::perform(
             async move {
                 rfd::AsyncFileDialog::new()
-                    .add_filter("Images", &["png", "jpg", "jpeg", "tga", "dds", "tiff"])
+                    .add_filter(
+                        "Images",
+                        &[
+                            "png", "jpg", "jpeg", "tga", "dds", "tif", "tiff", "bmp", "webp",
+                            "gif", "exr", "hdr",
+                        ],
+                    )
                     .pick_file()
                     .await
                     .map(|handle| handle.path().to_path_buf())
@@ -576,10 +1311,12 @@ impl TextureSplitter {
             self.state.status =
                 StatusMessage::info(format!("Loading image for slot {slot_idx}..."));
 
+            // Decode and build the preview off the UI thread
+            let float_range = self.float_range;
             Task::perform(
                 async move {
-                    match PorterImage::open(&path) {
-                        Ok(img) => Ok(img),
+                    match LoadedImage::open(&path, float_range) {
+                        Ok(loaded) => Ok(loaded),
                         Err(e) => Err(format!("Failed to load image: {e}")),
                     }
                 },
@@ -601,21 +1338,31 @@ impl TextureSplitter {
     fn on_input_image_loaded(
         &mut self,
         slot_idx: usize,
-        result: Result<PorterImage, String>,
+        result: Result<LoadedImage, String>,
     ) -> Task<Message> {
         match result {
-            Ok(img) => {
+            Ok(loaded) => {
                 if slot_idx < self.state.input_slots.len() {
-                    self.state.input_slots[slot_idx].load_image(img);
-                    // Update cached handle for this slot
-                    self.state.update_input_slot_handle(slot_idx);
-                    self.state.status =
-                        StatusMessage::success(format!("Loaded image for slot {slot_idx}"));
+                    self.record_history();
+                    let remap = self.load_into_slot(slot_idx, loaded);
+                    let displays = self.refresh_displays();
+                    self.state.status = match remap {
+                        Some(remap) => StatusMessage::warning(remap_warning(slot_idx, &remap)),
+                        None => StatusMessage::success(format!("Loaded image for slot {slot_idx}")),
+                    };
+
+                    let stats_task = self.refresh_stats_for(PreviewTarget::Input(slot_idx));
 
                     // If all required slots are filled, trigger merge
                     if self.state.all_required_slots_filled() {
-                        return self.trigger_merge_from_slots();
+                        return Task::batch([
+                            displays,
+                            stats_task,
+                            self.trigger_merge_from_slots(),
+                        ]);
                     }
+
+                    return Task::batch([displays, stats_task]);
                 }
             }
             Err(e) => {
@@ -625,28 +1372,102 @@ impl TextureSplitter {
         Task::none()
     }
 
+    /// Put a loaded image into a slot, with its preview and EXR layers
+    ///
+    /// Returns how a float file's values were remapped, to warn about.
+    fn load_into_slot(&mut self, slot_idx: usize, loaded: LoadedImage) -> Option<FloatRemap> {
+        if self.state.material_name.is_none() {
+            self.state.material_name = self.material_name(&loaded.path);
+        }
+        self.state.input_slots[slot_idx].load_image(loaded.image);
+        // Update cached preview for this slot
+        self.state
+            .update_input_slot_preview(slot_idx, Some(loaded.preview), Some(loaded.pixels));
+        self.state
+            .set_input_slot_layers(slot_idx, loaded.layers.map(|layers| (loaded.path, layers)));
+        loaded.remap
+    }
+
+    /// Handle a layer picked for a slot loaded from a multi-layer EXR
+    ///
+    /// Reloads the slot's file with the chosen layer.
+    fn on_input_layer_selected(&mut self, slot_idx: usize, layer: String) -> Task<Message> {
+        let Some((path, _)) = self
+            .state
+            .input_slot_layers
+            .get(slot_idx)
+            .and_then(|layers| layers.clone())
+        else {
+            return Task::none();
+        };
+
+        self.state.status =
+            StatusMessage::info(format!("Loading layer {layer} for slot {slot_idx}..."));
+
+        let float_range = self.float_range;
+        Task::perform(
+            async move {
+                LoadedImage::open_layer(&path, Some(&layer), float_range)
+                    .map_err(|e| format!("Failed to load layer: {e}"))
+            },
+            move |result| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::InputImageLoaded(slot_idx, result),
+                ))
+            },
+        )
+    }
+
     /// Trigger GPU processing with current input images
     ///
     /// Collects images from all slots and processes them using the selected shader
     /// with current parameter values. Uses generation counter to handle concurrent requests.
     fn trigger_merge_from_slots(&mut self) -> Task<Message> {
+        self.trigger_merge(false)
+    }
+
+    /// Trigger a full-resolution or preview render
+    ///
+    /// Previews cap the output at `PREVIEW_MAX_DIMENSION` so slider drags stay
+    /// responsive; their outputs can't be saved until a full render replaces
+    /// them.
+    fn trigger_merge(&mut self, preview: bool) -> Task<Message> {
         if !self.state.all_required_slots_filled() {
             return Task::none();
         }
 
         if let Some(shader) = self.state.get_selected_shader() {
-            // Collect images from slots
-            let mut images: Vec<Arc<PorterImage>> = Vec::new();
-            for slot in &self.state.input_slots {
-                if let Some(img) = &slot.image {
-                    images.push(Arc::clone(img));
-                }
-            }
+            // Collect images at their slot positions, with generations for
+            // the texture cache
+            let images: Vec<Option<Arc<PorterImage>>> = self
+                .state
+                .input_slots
+                .iter()
+                .map(|slot| slot.image.clone())
+                .collect();
+            let generations: Vec<u64> = (0..images.len())
+                .map(|idx| {
+                    self.state
+                        .input_slot_generations
+                        .get(idx)
+                        .copied()
+                        .unwrap_or_default()
+                })
+                .collect();
 
-            if images.is_empty() {
+            if images.iter().all(Option::is_none) {
                 return Task::none();
             }
 
+            // Multi-frame inputs render the selected frame
+            let images = match frames::frame_inputs(&images, self.state.current_frame()) {
+                Ok(images) => images,
+                Err(e) => {
+                    self.state.status = StatusMessage::error(e);
+                    return Task::none();
+                }
+            };
+
             // Get parameters
             let shader_name = shader.shader.name.clone();
             let param_values = self
@@ -656,13 +1477,47 @@ impl TextureSplitter {
                 .cloned()
                 .unwrap_or_default();
 
-            self.state.processing = true;
+            // A newer merge replaces the running one, so stop it early
+            if let Some(previous) = self.state.process_job.take() {
+                self.state.jobs.abandon(previous);
+            }
+
+            let progress = self
+                .state
+                .jobs
+                .start(JobKind::Process, "Processing", &self.controller);
+            self.state.process_job = Some(progress.id());
             self.state.merge_generation += 1;
+            self.state.merge_is_preview = preview;
+            self.state.preview_stale = false;
             let generation = self.state.merge_generation;
-            self.state.status = StatusMessage::info("Processing...");
+            let max_output_dimension = preview.then_some(PREVIEW_MAX_DIMENSION);
+            let render = self.render_options();
+            if !preview {
+                self.state.status = StatusMessage::info("Processing...");
+            }
 
             Task::perform(
-                process_images(images, shader, param_values),
+                async move {
+                    let outputs = process_images(
+                        images,
+                        Some(generations),
+                        shader,
+                        param_values,
+                        max_output_dimension,
+                        &render,
+                        &progress,
+                    )
+                    .await;
+
+                    // Build previews off the UI thread
+                    outputs.map(|outputs| {
+                        outputs
+                            .into_iter()
+                            .map(|(buffer, description)| ProcessedOutput::new(buffer, description))
+                            .collect::<Vec<_>>()
+                    })
+                },
                 move |result| {
                     Message::Main(crate::windows::MainMessage::TextureSplitter(
                         TextureSplitterMessage::MergeCompleted(result, generation),
@@ -674,25 +1529,45 @@ impl TextureSplitter {
         }
     }
 
-    /// Save all output images to a selected folder
+    /// Save the selected output images to a selected folder
     ///
-    /// Opens folder picker and saves all outputs with auto-generated filenames
-    /// using the currently selected image format.
+    /// Opens folder picker and saves every output not unticked in the output
+    /// list, with templated filenames in each output's chosen image format.
     fn on_save_all(&mut self) -> Task<Message> {
-        if !self.state.is_saving && !self.state.output_buffers.is_empty() {
-            self.state.is_saving = true;
-            self.state.status = StatusMessage::info("Saving all outputs...");
-
-            // Clone all output buffers and descriptions
-            let outputs: Vec<(ImageBuffer, String)> = self
+        if self.state.save_job.is_none()
+            && !self.state.outputs_are_preview
+            && !self.state.output_buffers.is_empty()
+        {
+            let formats = self.save_formats();
+            let options = formats.options;
+            let policy = self.save_policy;
+
+            // The selected outputs, named after their descriptions
+            let (descriptions, file_names): (Vec<String>, Vec<String>) = self
                 .state
-                .output_buffers
+                .output_descriptions
                 .iter()
-                .zip(self.state.output_descriptions.iter())
-                .map(|(buffer, desc)| (buffer.clone(), desc.clone()))
-                .collect();
+                .filter(|desc| !self.state.skipped_outputs.contains(*desc))
+                .map(|desc| {
+                    let file_name = description_file_name(desc, formats.for_output(desc));
+                    (desc.clone(), file_name)
+                })
+                .unzip();
+
+            if descriptions.is_empty() {
+                self.state.status = StatusMessage::warning("No outputs are selected for saving.");
+                return Task::none();
+            }
+            let Some(source) = self.frame_source(&descriptions) else {
+                return Task::none();
+            };
 
-            let format = self.selected_format;
+            let progress = self
+                .state
+                .jobs
+                .start(JobKind::Save, "Saving outputs", &self.controller);
+            self.state.save_job = Some(progress.id());
+            self.state.status = StatusMessage::info("Saving outputs...");
 
             Task::perform(
                 async move {
@@ -703,44 +1578,39 @@ impl TextureSplitter {
 
                     if let Some(folder_handle) = folder {
                         let folder_path = folder_handle.path().to_path_buf();
-                        let mut saved_paths = Vec::new();
-
-                        for (buffer, description) in outputs {
-                            let filename = format!(
-                                "{}.{}",
-                                description
-                                    .to_lowercase()
-                                    .replace(" ", "_")
-                                    .replace("/", "_")
-                                    .replace("\\", "_"),
-                                format.extension()
-                            );
-
-                            let file_path = folder_path.join(&filename);
-
-                            std::fs::create_dir_all(file_path.parent().unwrap_or(&file_path))
-                                .map_err(|e| format!("Failed to create directory: {e}"))?;
-
-                            match buffer.into_porter_image() {
-                                Ok(mut img) => match img.save(&file_path) {
-                                    Ok(_) => {
-                                        saved_paths.push(file_path);
-                                    }
-                                    Err(e) => {
-                                        return Err(format!("Failed to save {filename}: {e}"));
-                                    }
-                                },
-                                Err(e) => {
-                                    return Err(format!(
-                                        "Failed to convert buffer for {filename}: {e}"
-                                    ));
+
+                        // Resolve conflicts for every output before writing any
+                        let destinations: Vec<PathBuf> = file_names
+                            .iter()
+                            .map(|filename| folder_path.join(filename))
+                            .collect();
+                        let policy = policy
+                            .resolve(&destinations)
+                            .await
+                            .ok_or_else(|| jobs::CANCELLED.to_string())?;
+
+                        // Multi-frame inputs render every frame before saving
+                        let outputs = source.outputs(&descriptions, &progress).await?;
+
+                        let mut to_save = Vec::new();
+                        for ((output, filename), destination) in
+                            outputs.into_iter().zip(file_names).zip(destinations)
+                        {
+                            match policy.destination(destination) {
+                                Some(file_path) => {
+                                    std::fs::create_dir_all(
+                                        file_path.parent().unwrap_or(&file_path),
+                                    )
+                                    .map_err(|e| format!("Failed to create directory: {e}"))?;
+                                    to_save.push((output, file_path));
                                 }
+                                None => tracing::info!("Skipped existing file: {}", filename),
                             }
                         }
 
-                        Ok(saved_paths)
+                        parallel::save_images(to_save, options, progress).await
                     } else {
-                        Err("Save cancelled".to_string())
+                        Err(jobs::CANCELLED.to_string())
                     }
                 },
                 |result| {
@@ -754,23 +1624,422 @@ impl TextureSplitter {
         }
     }
 
+    /// Ask for an output folder for the selected batch groups
+    fn on_batch_process(&mut self) -> Task<Message> {
+        let Some(shader) = self.state.get_selected_shader() else {
+            return Task::none();
+        };
+        let Some(queue) = self.state.batch.clone().filter(|queue| {
+            !queue.is_running() && !queue.runnable_groups(&shader.inputs).is_empty()
+        }) else {
+            return Task::none();
+        };
+        let formats = self.save_formats();
+        let policy = self.save_policy;
+        let skipped = self.state.skipped_outputs.clone();
+
+        if shader
+            .outputs
+            .iter()
+            .all(|output| skipped.contains(&output.description))
+        {
+            self.state.status = StatusMessage::warning("No outputs are selected for saving.");
+            return Task::none();
+        }
+
+        // Materials of the same name from different folders would overwrite
+        // each other's outputs
+        let clashes = queue.clashing_outputs(&shader, &formats, &skipped);
+        if let Some(first) = clashes.first() {
+            self.state.status = StatusMessage::error(format!(
+                "{} output(s) would be written by more than one material, such as {}. \
+                 Deselect the duplicate materials to continue.",
+                clashes.len(),
+                first.display()
+            ));
+            return Task::none();
+        }
+
+        Task::perform(
+            async move {
+                let folder = rfd::AsyncFileDialog::new()
+                    .set_title("Select folder to save batch outputs")
+                    .pick_folder()
+                    .await?
+                    .path()
+                    .to_path_buf();
+
+                // Resolve conflicts for the whole batch before processing starts
+                let planned = queue.planned_outputs(&shader, &formats, &skipped, &folder);
+                let policy = policy.resolve(&planned).await?;
+
+                Some((folder, policy))
+            },
+            |selection| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::BatchFolderSelected(selection),
+                ))
+            },
+        )
+    }
+
+    /// Start processing the selected batch groups into a folder
+    ///
+    /// The current shader, parameters, formats and output selection are used
+    /// for every group.
+    fn on_batch_folder_selected(
+        &mut self,
+        selection: Option<(PathBuf, SavePolicy)>,
+    ) -> Task<Message> {
+        let Some((output_folder, policy)) = selection else {
+            self.state.status = StatusMessage::info("Batch cancelled");
+            return Task::none();
+        };
+        let Some(shader) = self.state.get_selected_shader() else {
+            return Task::none();
+        };
+        let parameter_values = self
+            .state
+            .parameter_values
+            .get(&shader.shader.name)
+            .cloned()
+            .unwrap_or_default();
+        let formats = self.save_formats();
+
+        let Some(queue) = self
+            .state
+            .batch
+            .as_mut()
+            .filter(|queue| !queue.is_running())
+        else {
+            return Task::none();
+        };
+
+        let pending = queue.runnable_groups(&shader.inputs);
+        let progress = self
+            .state
+            .jobs
+            .start(JobKind::Batch, "Batch", &self.controller);
+        queue.run = Some(BatchRun {
+            shader,
+            parameter_values,
+            formats,
+            skipped: self.state.skipped_outputs.clone(),
+            float_range: self.float_range,
+            policy,
+            output_folder,
+            progress,
+            render_lock: Default::default(),
+            total: pending.len(),
+            pending,
+            in_flight: 0,
+            peak_group_bytes: 0,
+            completed: 0,
+            saved_files: 0,
+            failures: Vec::new(),
+        });
+
+        self.process_next_batch_group()
+    }
+
+    /// Start waiting batch groups while the pipeline has room, or finish
+    /// the batch once every group is done
+    fn process_next_batch_group(&mut self) -> Task<Message> {
+        let Some(queue) = self.state.batch.as_mut() else {
+            return Task::none();
+        };
+        let Some(run) = queue.run.as_mut() else {
+            return Task::none();
+        };
+        let job = run.progress.id();
+        let cancelled = self.state.jobs.is_cancelled(job);
+
+        // Groups already started finish before the batch ends
+        if cancelled && run.in_flight == 0 {
+            self.state.status = StatusMessage::warning(format!(
+                "Batch cancelled after {} of {} material{}",
+                run.completed,
+                run.total,
+                if run.total == 1 { "" } else { "s" }
+            ));
+            self.state.jobs.finish(job);
+            queue.run = None;
+            return Task::none();
+        }
+
+        if run.is_finished() {
+            self.state.jobs.finish(job);
+            self.state.status = if run.failures.is_empty() {
+                StatusMessage::success(format!(
+                    "Batch complete: saved {} file{} for {} material{}",
+                    run.saved_files,
+                    if run.saved_files == 1 { "" } else { "s" },
+                    run.total,
+                    if run.total == 1 { "" } else { "s" }
+                ))
+            } else {
+                StatusMessage::warning(format!(
+                    "Batch finished with {} failure{}: {}",
+                    run.failures.len(),
+                    if run.failures.len() == 1 { "" } else { "s" },
+                    run.failures[0]
+                ))
+            };
+            queue.run = None;
+            return Task::none();
+        }
+
+        let mut tasks = Vec::new();
+        while !cancelled && run.can_start_group() {
+            let idx = run.pending.remove(0);
+            let group = queue.groups[idx].clone();
+            run.in_flight += 1;
+
+            let label = format!(
+                "Batch: {} ({}/{})",
+                group.name,
+                run.completed + run.in_flight,
+                run.total
+            );
+            self.state.jobs.set_label(job, &label);
+            self.state.status = StatusMessage::info(format!("{label}..."));
+
+            tasks.push(Task::perform(
+                batch::process_group(
+                    group,
+                    run.shader.clone(),
+                    run.parameter_values.clone(),
+                    run.formats.clone(),
+                    run.skipped.clone(),
+                    run.float_range,
+                    run.policy,
+                    run.output_folder.clone(),
+                    run.progress.clone(),
+                    run.render_lock.clone(),
+                ),
+                move |result| {
+                    Message::Main(crate::windows::MainMessage::TextureSplitter(
+                        TextureSplitterMessage::BatchGroupProcessed(idx, result),
+                    ))
+                },
+            ));
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Record the result of one batch group and start the next ones
+    fn on_batch_group_processed(
+        &mut self,
+        idx: usize,
+        result: Result<GroupOutput, String>,
+    ) -> Task<Message> {
+        let Some(run) = self
+            .state
+            .batch
+            .as_mut()
+            .and_then(|queue| queue.run.as_mut())
+        else {
+            return Task::none();
+        };
+
+        run.in_flight = run.in_flight.saturating_sub(1);
+        match result {
+            Ok(output) => {
+                tracing::info!(
+                    "Batch group {} saved {} files",
+                    idx,
+                    output.saved_paths.len()
+                );
+                run.completed += 1;
+                run.saved_files += output.saved_paths.len();
+                run.peak_group_bytes = run.peak_group_bytes.max(output.image_bytes);
+            }
+            Err(e) if e == jobs::CANCELLED => {
+                tracing::info!("Batch group {} cancelled", idx);
+            }
+            Err(e) => {
+                tracing::error!("Batch group {} failed: {}", idx, e);
+                run.completed += 1;
+                run.failures.push(e);
+            }
+        }
+
+        self.process_next_batch_group()
+    }
+
+    /// Render the batch queue
+    fn view_batch<'a>(&'a self, queue: &'a BatchQueue) -> Element<'a, TextureSplitterMessage> {
+        use iced::widget::{Column, checkbox, scrollable};
+
+        let inputs = self
+            .state
+            .get_selected_shader()
+            .map(|shader| shader.inputs)
+            .unwrap_or_default();
+        let running = queue.is_running();
+
+        let mut list = Column::new().spacing(6);
+        for (idx, group) in queue.groups.iter().enumerate() {
+            let missing = group.missing_inputs(&inputs);
+            let details = if missing.is_empty() {
+                group
+                    .files
+                    .iter()
+                    .flatten()
+                    .filter_map(|path| path.file_name())
+                    .map(|name| name.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            } else {
+                format!("Missing: {}", missing.join(", "))
+            };
+
+            let toggle = checkbox(group.name.as_str(), group.selected).on_toggle_maybe(
+                (!running && missing.is_empty()).then_some(move |selected| {
+                    TextureSplitterMessage::BatchGroupToggled(idx, selected)
+                }),
+            );
+
+            list = list.push(
+                column![toggle, text(details).size(11)]
+                    .spacing(2)
+                    .width(Length::Fill),
+            );
+        }
+
+        let mut summary = format!(
+            "{} material{} found",
+            queue.groups.len(),
+            if queue.groups.len() == 1 { "" } else { "s" }
+        );
+        if queue.unmatched_files > 0 {
+            summary.push_str(&format!(
+                ", {} file{} matched no input",
+                queue.unmatched_files,
+                if queue.unmatched_files == 1 { "" } else { "s" }
+            ));
+        }
+
+        let runnable = queue.runnable_groups(&inputs).len();
+        let process_label = match &queue.run {
+            Some(run) => format!("Processing {}/{}...", run.completed, run.total),
+            None => format!("Process {runnable} selected"),
+        };
+        let process_button = button(text(process_label))
+            .on_press_maybe(
+                (!running && runnable > 0).then_some(TextureSplitterMessage::BatchProcessPressed),
+            )
+            .padding(12)
+            .style(crate::widget_helpers::primary_button_style);
+        let close_button = button("Close batch")
+            .on_press_maybe((!running).then_some(TextureSplitterMessage::BatchClosed))
+            .padding(12)
+            .style(crate::widget_helpers::secondary_button_style);
+
+        let mut content = column![
+            text(summary).size(16),
+            container(scrollable(list).height(Length::Fill))
+                .padding(10)
+                .height(Length::Fill)
+                .style(crate::widget_helpers::frame_style),
+            row![container(process_button).width(Length::Fill), close_button].spacing(10),
+        ];
+        if let Some(jobs) = self.view_jobs() {
+            content = content.push(jobs);
+        }
+
+        content
+            .push(
+                text(&self.state.status.message)
+                    .size(12)
+                    .color(self.state.status.colour()),
+            )
+            .spacing(10)
+            .padding(20)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
     /// Clear all input images and outputs
     ///
     /// Resets the component to its initial state, clearing all loaded images
     /// and processing results.
     fn on_clear(&mut self) -> Task<Message> {
+        self.record_history();
         self.state.clear_inputs();
         self.state.clear_outputs();
+        self.state.view = viewport::ViewTransform::default();
         self.state.status = StatusMessage::info("Cleared all images.");
         tracing::info!("Cleared all loaded images");
         Task::none()
     }
 
+    /// Record the current state as an undo step
+    fn record_history(&mut self) {
+        let snapshot = self.state.snapshot();
+        self.state.history.record(snapshot);
+    }
+
+    /// Handle undo
+    ///
+    /// Restores the previous snapshot and re-processes the outputs.
+    fn on_undo(&mut self) -> Task<Message> {
+        let current = self.state.snapshot();
+        match self.state.history.undo(current) {
+            Some(snapshot) => {
+                self.state.restore(snapshot);
+                self.state.status = StatusMessage::info("Undone");
+                self.after_history_restore()
+            }
+            None => Task::none(),
+        }
+    }
+
+    /// Handle redo
+    ///
+    /// Restores the next snapshot and re-processes the outputs.
+    fn on_redo(&mut self) -> Task<Message> {
+        let current = self.state.snapshot();
+        match self.state.history.redo(current) {
+            Some(snapshot) => {
+                self.state.restore(snapshot);
+                self.state.status = StatusMessage::info("Redone");
+                self.after_history_restore()
+            }
+            None => Task::none(),
+        }
+    }
+
+    /// Refresh derived state after a snapshot is restored
+    fn after_history_restore(&mut self) -> Task<Message> {
+        // Drop any pending debounce so it can't record over the restored state
+        self.state.parameter_debounce_generation += 1;
+        self.state.parameter_edit_pending = false;
+        if let Some(job) = self.state.process_job.take() {
+            self.state.jobs.abandon(job);
+        }
+        self.state.merge_generation += 1;
+
+        let displays = self.refresh_displays();
+        let stats_task = self.refresh_stats();
+
+        if self.state.all_required_slots_filled() {
+            Task::batch([displays, stats_task, self.trigger_merge_from_slots()])
+        } else {
+            Task::batch([displays, stats_task])
+        }
+    }
+
     /// Handle save all completion
     ///
     /// Updates status with success/failure message after batch save operation.
     fn on_all_images_saved(&mut self, result: Result<Vec<PathBuf>, String>) -> Task<Message> {
-        self.state.is_saving = false;
+        if let Some(job) = self.state.save_job.take() {
+            self.state.jobs.finish(job);
+        }
+
         match result {
             Ok(paths) => {
                 self.state.status =
@@ -779,6 +2048,9 @@ impl TextureSplitter {
                     tracing::info!("Saved output to: {}", path.display());
                 }
             }
+            Err(e) if e == jobs::CANCELLED => {
+                self.state.status = StatusMessage::warning("Save cancelled");
+            }
             Err(e) => {
                 self.state.status = StatusMessage::error(format!("Save all failed: {e}"));
                 tracing::error!("Save all failed: {}", e);
@@ -787,6 +2059,258 @@ impl TextureSplitter {
         Task::none()
     }
 
+    /// Slot targeted by keyboard shortcuts
+    ///
+    /// The last hovered or browsed slot, otherwise the first empty slot.
+    fn focused_slot(&self) -> Option<usize> {
+        self.state
+            .focused_slot
+            .filter(|idx| *idx < self.state.input_slots.len())
+            .or_else(|| {
+                self.state
+                    .input_slots
+                    .iter()
+                    .position(|slot| slot.image.is_none())
+            })
+            .or_else(|| (!self.state.input_slots.is_empty()).then_some(0))
+    }
+
+    /// Where a save of the given outputs gets their frames from
+    ///
+    /// Outputs of single-frame inputs are already rendered; multi-frame
+    /// inputs are rendered again for every frame.
+    fn frame_source(&self, descriptions: &[String]) -> Option<FrameSource> {
+        let layout = self.state.frame_layout();
+        let shader_config = self.state.get_selected_shader()?;
+        if !layout.is_multi_frame() {
+            let outputs = self
+                .state
+                .output_buffers
+                .iter()
+                .zip(&self.state.output_descriptions)
+                .filter(|(_, description)| descriptions.contains(description))
+                .map(|(buffer, description)| {
+                    let alpha_mode = shader_config.output_alpha_mode(description);
+                    (
+                        FramedOutput::single(ImageBuffer::clone(buffer), layout, alpha_mode),
+                        description.clone(),
+                    )
+                })
+                .collect();
+
+            return Some(FrameSource::Rendered { outputs });
+        }
+
+        let parameter_values = self
+            .state
+            .parameter_values
+            .get(&shader_config.shader.name)
+            .cloned()
+            .unwrap_or_default();
+        let images = self
+            .state
+            .input_slots
+            .iter()
+            .map(|slot| slot.image.clone())
+            .collect();
+
+        Some(FrameSource::Inputs {
+            images,
+            layout,
+            shader_config,
+            parameter_values,
+            render: self.render_options(),
+        })
+    }
+
+    /// Formats and encoder settings outputs are saved with
+    fn save_formats(&self) -> SaveFormats {
+        SaveFormats {
+            default: self.selected_format,
+            overrides: self.state.output_formats.clone(),
+            options: SaveOptions {
+                ktx2: self.ktx2_settings,
+            },
+            render: self.render_options(),
+        }
+    }
+
+    /// Dithering and size each output is rendered with
+    fn render_options(&self) -> RenderOptions {
+        RenderOptions {
+            dithering: Dithering {
+                default: self.dither_mode,
+                overrides: self.state.output_dithers.clone(),
+            },
+            sizes: OutputSizes {
+                overrides: self.state.output_sizes.clone(),
+                power_of_two: self.power_of_two_sizes,
+            },
+        }
+    }
+
+    /// Outputs rendered smaller than their configured size, and the size
+    /// each was capped to
+    fn capped_outputs(&self, outputs: &[ProcessedOutput]) -> Option<String> {
+        let shader = self.state.get_selected_shader()?;
+        let base = self
+            .state
+            .input_slots
+            .iter()
+            .find_map(|slot| slot.image.as_ref())?
+            .dimensions();
+        let sizes = self.render_options().sizes;
+
+        let capped: Vec<String> = shader
+            .outputs
+            .iter()
+            .zip(outputs)
+            .filter_map(|(config, output)| {
+                let (width, height) = sizes.output_size(config, base);
+                let (capped_width, capped_height) = output.buffer.dimensions();
+                ((capped_width, capped_height) != (width, height)).then(|| {
+                    format!(
+                        "{} capped to {capped_width}x{capped_height} from {width}x{height}",
+                        output.description
+                    )
+                })
+            })
+            .collect();
+        (!capped.is_empty()).then(|| capped.join(", "))
+    }
+
+    /// Render the outputs again after a setting they depend on changed
+    fn rerender_outputs(&mut self) -> Task<Message> {
+        if self.state.output_buffers.is_empty() {
+            return Task::none();
+        }
+        self.trigger_merge_from_slots()
+    }
+
+    /// File name offered by Save This Output
+    ///
+    /// Once textures have been loaded from disk this is the material name
+    /// with the output's suffix (`wall_nog.png`), matching batch output.
+    /// Otherwise it falls back to the output description, which Save All
+    /// always uses.
+    fn output_file_name(&self, description: &str, format: ImageFormat) -> String {
+        let suffix = self.state.get_selected_shader().and_then(|shader| {
+            shader
+                .outputs
+                .into_iter()
+                .find(|output| output.description == description)
+                .map(|output| output.suffix)
+        });
+
+        match (&self.state.material_name, suffix) {
+            (Some(material), Some(suffix)) => {
+                format!("{material}{suffix}.{}", format.extension())
+            }
+            _ => description_file_name(description, format),
+        }
+    }
+
+    /// Base name of a loaded texture, without its input suffix
+    fn material_name(&self, path: &std::path::Path) -> Option<String> {
+        let inputs = self
+            .state
+            .get_selected_shader()
+            .map(|shader| shader.inputs)
+            .unwrap_or_default();
+
+        slot_matching::match_slot(&inputs, path)
+            .map(|found| found.base)
+            .or_else(|| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(str::to_string)
+            })
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Save the current output with a Save As dialog
+    fn on_save_current(&mut self) -> Task<Message> {
+        if self.state.save_job.is_some() || self.state.outputs_are_preview {
+            return Task::none();
+        }
+
+        let Some((_, description)) = self.state.get_current_output() else {
+            return Task::none();
+        };
+
+        let descriptions = vec![description.clone()];
+        let formats = self.save_formats();
+        let format = formats.for_output(description);
+        let options = formats.options;
+        let file_name = self.output_file_name(description, format);
+        let Some(source) = self.frame_source(&descriptions) else {
+            return Task::none();
+        };
+
+        let progress =
+            self.state
+                .jobs
+                .start(JobKind::Save, "Saving current output", &self.controller);
+        self.state.save_job = Some(progress.id());
+        self.state.status = StatusMessage::info("Saving current output...");
+
+        Task::perform(
+            async move {
+                let file = rfd::AsyncFileDialog::new()
+                    .set_title("Save output")
+                    .set_file_name(&file_name)
+                    .add_filter(format.display_name(), &[format.extension()])
+                    .save_file()
+                    .await
+                    .ok_or_else(|| jobs::CANCELLED.to_string())?;
+
+                let path = file.path().to_path_buf();
+                let output = source
+                    .outputs(&descriptions, &progress)
+                    .await?
+                    .pop()
+                    .ok_or_else(|| format!("No output to save for {file_name}"))?;
+
+                progress.stage(JobStage::Encode)?;
+                let mut image = output
+                    .into_porter_image()
+                    .map_err(|e| format!("Failed to convert buffer for {file_name}: {e}"))?;
+                image
+                    .save_atomic(&path, &options)
+                    .map_err(|e| format!("Failed to save {}: {e}", path.display()))?;
+
+                Ok::<_, String>(path)
+            },
+            |result| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::CurrentImageSaved(result),
+                ))
+            },
+        )
+    }
+
+    /// Handle save current completion
+    fn on_current_image_saved(&mut self, result: Result<PathBuf, String>) -> Task<Message> {
+        if let Some(job) = self.state.save_job.take() {
+            self.state.jobs.finish(job);
+        }
+
+        match result {
+            Ok(path) => {
+                self.state.status = StatusMessage::success(format!("Saved {}", path.display()));
+                tracing::info!("Saved output to: {}", path.display());
+            }
+            Err(e) if e == jobs::CANCELLED => {
+                self.state.status = StatusMessage::warning("Save cancelled");
+            }
+            Err(e) => {
+                self.state.status = StatusMessage::error(format!("Save failed: {e}"));
+                tracing::error!("Save failed: {}", e);
+            }
+        }
+        Task::none()
+    }
+
     /// Render an input slot using cached image handle
     ///
     /// Uses cached image handles to avoid regenerating them every frame.
@@ -796,31 +2320,22 @@ impl TextureSplitter {
         idx: usize,
         slot: &'a DroppableImageSlot,
     ) -> Element<'a, TextureSplitterMessage> {
-        use crate::widget_helpers::{control, primary_button_style};
+        use crate::widget_helpers::{control, pick_list_style, primary_button_style};
         use iced::widget::{button, column, container};
 
         // Make slot size responsive based on number of inputs
-        let preview_size = match self.state.input_slots.len() {
-            1 => SLOT_SIZE_SINGLE,
-            2 => SLOT_SIZE_TWO,
-            3 => SLOT_SIZE_THREE,
-            4 => SLOT_SIZE_FOUR,
-            _ => SLOT_SIZE_MANY,
-        };
+        let preview_size = slot_preview_size(self.state.input_slots.len());
 
         // Create the image preview using cached handle or placeholder
-        let image_widget = if let Some(cached_handle) = self.state.get_input_slot_handle(idx) {
+        let image_widget = if let Some(cached_handle) =
+            self.state.get_input_slot_handle(idx, preview_size)
+        {
             // Use cached handle - no conversion needed!
-            container(
-                iced::widget::image(cached_handle.clone())
-                    .content_fit(iced::ContentFit::Cover)
-                    .width(preview_size)
-                    .height(preview_size),
-            )
-            .width(preview_size)
-            .height(preview_size)
-            .center_x(Length::Fill)
-            .center_y(Length::Fill)
+            container(self.view_zoomable(PreviewTarget::Input(idx), cached_handle, preview_size))
+                .width(preview_size)
+                .height(preview_size)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
         } else {
             // Show placeholder
             container(
@@ -841,47 +2356,1107 @@ impl TextureSplitter {
             .width(preview_size)
             .style(primary_button_style);
 
-        let col = column![image_widget, browse_button]
+        let mut col = column![image_widget]
             .spacing(8)
             .align_x(iced::Alignment::Center);
 
+        if slot.image.is_some() {
+            col = col.push(self.view_channel_toggles(PreviewTarget::Input(idx)));
+        }
+
+        if let Some((_, layers)) = self
+            .state
+            .input_slot_layers
+            .get(idx)
+            .and_then(|layers| layers.as_ref())
+        {
+            col = col.push(
+                pick_list(
+                    layers.names.as_slice(),
+                    Some(layers.selected.clone()),
+                    move |layer| TextureSplitterMessage::InputLayerSelected(idx, layer),
+                )
+                .text_size(12)
+                .width(preview_size)
+                .style(pick_list_style),
+            );
+        }
+
+        let col = col.push(browse_button);
+
         control(text(&slot.label).size(13).into(), col.into()).into()
     }
 
-    /// Handle GPU processing completion
+    /// Render a preview that can be zoomed, panned and inspected
     ///
-    /// Updates state with processing results and converts output buffers to
-    /// displayable image handles. Uses generation counter to ignore stale results.
-    fn on_merge_completed(
-        &mut self,
-        result: Result<Vec<(ImageBuffer, String)>, String>,
-        generation: u64,
-    ) -> Task<Message> {
-        if generation != self.state.merge_generation {
-            return Task::none();
-        }
+    /// Shows the rendered display handle for `target`, falling back to the
+    /// fitted preview handle. Mouse input is reported back for the shared view.
+    fn view_zoomable(
+        &self,
+        target: PreviewTarget,
+        fitted_handle: &iced::widget::image::Handle,
+        size: f32,
+    ) -> Element<'_, TextureSplitterMessage> {
+        let handle = self
+            .state
+            .get_display_handle(target)
+            .unwrap_or(fitted_handle);
+
+        self.view_interactive(target, handle, size)
+    }
 
-        self.state.processing = false;
+    /// Render a handle that reports mouse input as `target`
+    fn view_interactive(
+        &self,
+        target: PreviewTarget,
+        handle: &iced::widget::image::Handle,
+        size: f32,
+    ) -> Element<'_, TextureSplitterMessage> {
+        use iced::widget::image::FilterMethod;
+        use iced::widget::mouse_area;
+
+        let handle = handle.clone();
+
+        // Zoomed views are already at display resolution with hard texel edges
+        let filter_method = if self.state.view.is_fitted() {
+            FilterMethod::Linear
+        } else {
+            FilterMethod::Nearest
+        };
 
-        match result {
-            Ok(outputs) => {
-                self.state.set_outputs(outputs);
+        let interaction = if self.state.dragging_split || self.is_over_split_divider() {
+            iced::mouse::Interaction::ResizingHorizontally
+        } else if self.state.dragging_preview && !self.state.view.is_fitted() {
+            iced::mouse::Interaction::Grabbing
+        } else if self.state.view.is_fitted() {
+            iced::mouse::Interaction::Crosshair
+        } else {
+            iced::mouse::Interaction::Grab
+        };
 
-                if self.state.outputs.len() > 1 {
-                    self.state.status = StatusMessage::success(format!(
-                        "Processing complete - {} outputs generated",
-                        self.state.outputs.len()
-                    ));
-                } else {
-                    self.state.status = StatusMessage::success("Processing complete");
+        mouse_area(
+            container(
+                iced::widget::image(handle)
+                    .content_fit(iced::ContentFit::Contain)
+                    .filter_method(filter_method)
+                    .width(size)
+                    .height(size),
+            )
+            .width(size)
+            .height(size),
+        )
+        .on_move(move |point| TextureSplitterMessage::PreviewHovered(target, point))
+        .on_exit(TextureSplitterMessage::PreviewExited)
+        .on_press(TextureSplitterMessage::PreviewPressed)
+        .on_release(TextureSplitterMessage::PreviewReleased)
+        .on_scroll(TextureSplitterMessage::PreviewScrolled)
+        .interaction(interaction)
+        .into()
+    }
+
+    /// Render the zoom controls and the pixel readout for the hovered texel
+    ///
+    /// The readout lists the same UV position in the current output and in
+    /// every loaded input, as bytes and as normalised floats.
+    fn view_inspector(&self) -> Element<'_, TextureSplitterMessage> {
+        use crate::widget_helpers::secondary_button_style;
+        use viewport::{format_texel, uv_to_texel};
+
+        let zoom_label = text(format!("Zoom {:.0}%", self.state.view.zoom * 100.0)).size(12);
+        let fit_button = button(text("Fit").size(12))
+            .on_press_maybe(
+                (!self.state.view.is_fitted()).then_some(TextureSplitterMessage::ResetZoom),
+            )
+            .padding([2, 8])
+            .style(secondary_button_style);
+
+        let mut readout = column![].spacing(2);
+
+        let hovered_uv = self.state.hovered_preview.and_then(|(target, point)| {
+            self.state.preview_dimensions(target).map(|dims| {
+                self.state
+                    .view
+                    .display_to_uv(point, dims, self.preview_size(target))
+            })
+        });
+
+        if let Some(uv) = hovered_uv {
+            let output = self
+                .state
+                .output_buffers
+                .get(self.state.current_output_index)
+                .map(|buffer| ("Output".to_string(), buffer.as_ref()));
+            let inputs =
+                self.state
+                    .input_slot_pixels
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, pixels)| {
+                        pixels
+                            .as_ref()
+                            .map(|pixels| (format!("Input {idx}"), pixels.as_ref()))
+                    });
+
+            for (label, buffer) in output.into_iter().chain(inputs) {
+                let texel = uv_to_texel(uv, buffer.dimensions())
+                    .and_then(|(x, y)| buffer.get_pixel(x, y).map(|pixel| ((x, y), pixel)));
+
+                if let Some((position, pixel)) = texel {
+                    readout = readout.push(text(format_texel(&label, position, pixel)).size(11));
                 }
             }
-            Err(e) => {
-                self.state.status = StatusMessage::error(format!("Processing failed: {e}"));
-            }
         }
 
-        Task::none()
+        let mut inspector = column![].spacing(4).align_x(iced::Alignment::Center);
+
+        if !self.state.outputs.is_empty() {
+            inspector = inspector
+                .push(self.view_channel_toggles(PreviewTarget::Output))
+                .push(self.view_compare_controls());
+        }
+
+        if let Some(frame_controls) = self.view_frame_controls() {
+            inspector = inspector.push(frame_controls);
+        }
+
+        inspector = inspector.push(
+            row![self.view_alpha_preview(), self.view_float_range()]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+        );
+
+        inspector
+            .push(
+                row![zoom_label, fit_button]
+                    .spacing(8)
+                    .align_y(iced::Alignment::Center),
+            )
+            .push(readout)
+            .into()
+    }
+
+    /// Get the display size of a preview target
+    fn preview_size(&self, target: PreviewTarget) -> f32 {
+        match target {
+            PreviewTarget::Input(_) => slot_preview_size(self.state.input_slots.len()),
+            // Side-by-side compare splits the output area in two
+            PreviewTarget::Output | PreviewTarget::CompareInput
+                if self.state.is_comparing()
+                    && self.state.compare_mode == CompareMode::SideBySide =>
+            {
+                OUTPUT_PREVIEW_SIZE / 2.0
+            }
+            PreviewTarget::Output | PreviewTarget::CompareInput => OUTPUT_PREVIEW_SIZE,
+        }
+    }
+
+    /// Handle the cursor moving over a preview
+    ///
+    /// Records the hovered point for the pixel readout and pans the shared
+    /// view while the mouse button is held.
+    fn on_preview_hovered(&mut self, target: PreviewTarget, point: iced::Point) -> Task<Message> {
+        if let PreviewTarget::Input(idx) = target {
+            self.state.focused_slot = Some(idx);
+        }
+
+        if self.state.dragging_split {
+            self.state.split_position = (point.x / self.preview_size(target)).clamp(0.0, 1.0);
+            self.state.hovered_preview = Some((target, point));
+            return self.refresh_compare_display();
+        }
+
+        let last_hover = self
+            .state
+            .hovered_preview
+            .filter(|(last_target, _)| self.state.dragging_preview && *last_target == target);
+
+        let dims = self.state.preview_dimensions(target);
+        self.state.hovered_preview = Some((target, point));
+
+        let Some(((_, last_point), dims)) = last_hover.zip(dims) else {
+            return Task::none();
+        };
+        self.state.view.pan_by(
+            point.x - last_point.x,
+            point.y - last_point.y,
+            dims,
+            self.preview_size(target),
+        );
+        self.refresh_displays()
+    }
+
+    /// Handle the scroll wheel over a preview
+    ///
+    /// Zooms the shared view around the hovered texel.
+    fn on_preview_scrolled(&mut self, delta: iced::mouse::ScrollDelta) -> Task<Message> {
+        let hovered = self.state.hovered_preview.and_then(|(target, point)| {
+            self.state
+                .preview_dimensions(target)
+                .map(|dims| (target, point, dims))
+        });
+
+        if let Some((target, point, dims)) = hovered {
+            self.state.view.zoom_at(
+                viewport::scroll_zoom_factor(delta),
+                point,
+                dims,
+                self.preview_size(target),
+            );
+            return self.refresh_displays();
+        }
+
+        Task::none()
+    }
+
+    /// Re-render the display handles for every preview
+    fn refresh_displays(&mut self) -> Task<Message> {
+        self.request_displays(DisplayScope::All)
+    }
+
+    /// Re-render the display handles used by compare mode
+    fn refresh_compare_display(&mut self) -> Task<Message> {
+        self.request_displays(DisplayScope::Compare)
+    }
+
+    /// Render display handles on the thread pool
+    ///
+    /// While a render is running the refresh is merged into the next one,
+    /// which starts when the running render finishes.
+    fn request_displays(&mut self, scope: DisplayScope) -> Task<Message> {
+        if self.state.displays_rendering {
+            self.state.pending_displays = self.state.pending_displays.max(Some(scope));
+            return Task::none();
+        }
+
+        let request = self.display_request(scope);
+        self.state.displays_rendering = true;
+
+        Task::perform(
+            async move {
+                parallel::spawn(move || request.render())
+                    .await
+                    .unwrap_or_default()
+            },
+            |rendered| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::DisplaysRendered(Box::new(rendered)),
+                ))
+            },
+        )
+    }
+
+    /// Snapshot the images and settings a display refresh renders from
+    ///
+    /// Zoomed views are rendered from the full-resolution pixels, fitted
+    /// views from the nearest preview pyramid level. Both apply the channel
+    /// and alpha interpretation selected for that preview. The compared input
+    /// follows the output's channel, and split mode renders both sides through
+    /// the view transform so the divider lines up exactly.
+    fn display_request(&self, scope: DisplayScope) -> DisplayRequest {
+        let source = |target: PreviewTarget,
+                      preview: &crate::preview::PreviewPyramid,
+                      pixels: &Arc<ImageBuffer>,
+                      channel: ChannelView| DisplaySource {
+            preview: preview.clone(),
+            pixels: pixels.clone(),
+            size: self.preview_size(target),
+            channel,
+            alpha: self.state.alpha_view(target),
+        };
+        let current = self.state.current_output_index;
+        let output = self
+            .state
+            .outputs
+            .get(current)
+            .zip(self.state.output_buffers.get(current))
+            .map(|(preview, pixels)| {
+                source(
+                    PreviewTarget::Output,
+                    preview,
+                    pixels,
+                    self.state.output_channel,
+                )
+            });
+
+        let compared = self
+            .state
+            .input_slot_previews
+            .get(self.state.compare_input)
+            .and_then(|preview| preview.as_ref())
+            .zip(
+                self.state
+                    .input_slot_pixels
+                    .get(self.state.compare_input)
+                    .and_then(|pixels| pixels.as_ref()),
+            )
+            .filter(|_| self.state.is_comparing())
+            .map(|(preview, pixels)| {
+                source(
+                    PreviewTarget::CompareInput,
+                    preview,
+                    pixels,
+                    self.state.output_channel,
+                )
+            });
+        let compare = compared.and_then(|input| match self.state.compare_mode {
+            CompareMode::Split => output.clone().map(|output| CompareRequest::Split {
+                input,
+                output: DisplaySource {
+                    size: self.preview_size(PreviewTarget::CompareInput),
+                    ..output
+                },
+                position: self.state.split_position,
+            }),
+            _ => Some(CompareRequest::Input(input)),
+        });
+
+        if scope == DisplayScope::Compare {
+            return DisplayRequest {
+                view: self.state.view,
+                inputs: Vec::new(),
+                output: None,
+                compare,
+            };
+        }
+
+        let inputs = (0..self.state.input_slots.len())
+            .map(|idx| {
+                let target = PreviewTarget::Input(idx);
+                let preview = self.state.input_slot_previews.get(idx)?.as_ref()?;
+                let pixels = self.state.input_slot_pixels.get(idx)?.as_ref()?;
+                Some(source(
+                    target,
+                    preview,
+                    pixels,
+                    self.state.channel_view(target),
+                ))
+            })
+            .collect();
+
+        DisplayRequest {
+            view: self.state.view,
+            inputs,
+            output,
+            compare,
+        }
+    }
+
+    /// Show display handles rendered on the thread pool
+    ///
+    /// Handles rendered from an image the preview no longer holds are
+    /// dropped; the refresh that replaced the image renders them again.
+    /// Starts any refresh asked for while this one was rendering.
+    fn on_displays_rendered(&mut self, rendered: RenderedDisplays) -> Task<Message> {
+        self.state.displays_rendering = false;
+
+        self.state
+            .input_displays
+            .resize(self.state.input_slots.len(), None);
+        for (idx, display) in rendered.inputs.into_iter().enumerate() {
+            let pixels = self
+                .state
+                .input_slot_pixels
+                .get(idx)
+                .and_then(Option::as_ref);
+            let handle = display.and_then(|display| display.handle_for(pixels));
+            let Some(slot) = self.state.input_displays.get_mut(idx) else {
+                continue;
+            };
+            if handle.is_some() || pixels.is_none() {
+                *slot = handle;
+            }
+        }
+
+        let current = self
+            .state
+            .output_buffers
+            .get(self.state.current_output_index);
+        if let Some(handle) = rendered
+            .output
+            .and_then(|display| display.handle_for(current))
+        {
+            self.state.output_display = Some(handle);
+        }
+
+        let compared = self
+            .state
+            .input_slot_pixels
+            .get(self.state.compare_input)
+            .and_then(Option::as_ref);
+        if !self.state.is_comparing() {
+            self.state.compare_input_display = None;
+            self.state.compare_display = None;
+        } else {
+            match rendered.compare {
+                Some(RenderedCompare::Split {
+                    input,
+                    output,
+                    handle,
+                }) if self.state.compare_mode == CompareMode::Split
+                    && compared.is_some_and(|pixels| Arc::ptr_eq(pixels, &input))
+                    && current.is_some_and(|pixels| Arc::ptr_eq(pixels, &output)) =>
+                {
+                    self.state.compare_input_display = None;
+                    self.state.compare_display = Some(handle);
+                }
+                Some(RenderedCompare::Input(display))
+                    if self.state.compare_mode != CompareMode::Split =>
+                {
+                    if let Some(handle) = display.handle_for(compared) {
+                        self.state.compare_input_display = Some(handle);
+                        self.state.compare_display = None;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match self.state.pending_displays.take() {
+            Some(scope) => self.request_displays(scope),
+            None => Task::none(),
+        }
+    }
+
+    /// Whether the cursor is close enough to the split divider to drag it
+    fn is_over_split_divider(&self) -> bool {
+        if !self.state.is_comparing() || self.state.compare_mode != CompareMode::Split {
+            return false;
+        }
+
+        self.state.hovered_preview.is_some_and(|(target, point)| {
+            let divider = self.state.split_position * self.preview_size(target);
+            target == PreviewTarget::Output
+                && (point.x - divider).abs() <= compare::SPLIT_GRAB_DISTANCE
+        })
+    }
+
+    /// Handle a compare mode being selected
+    ///
+    /// Falls back to the first loaded input if the compared slot is empty,
+    /// and starts the flicker timer for flicker mode.
+    fn on_compare_mode_selected(&mut self, mode: CompareMode) -> Task<Message> {
+        self.state.compare_mode = mode;
+        self.state.dragging_split = false;
+
+        let compared_loaded = self
+            .state
+            .input_slot_pixels
+            .get(self.state.compare_input)
+            .is_some_and(|p| p.is_some());
+        if !compared_loaded {
+            if let Some(idx) = self
+                .state
+                .input_slot_pixels
+                .iter()
+                .position(|p| p.is_some())
+            {
+                self.state.compare_input = idx;
+            }
+        }
+
+        // Any running flicker timer stops at its next tick
+        self.state.flicker_generation += 1;
+        self.state.flicker_showing_input = false;
+
+        let displays = self.refresh_displays();
+
+        if mode == CompareMode::Flicker {
+            Task::batch([
+                displays,
+                schedule_flicker_tick(self.state.flicker_generation),
+            ])
+        } else {
+            displays
+        }
+    }
+
+    /// Render the output area while comparing with an input
+    fn view_compare(
+        &self,
+        output_preview: &crate::preview::PreviewPyramid,
+    ) -> Element<'_, TextureSplitterMessage> {
+        let size = self.preview_size(PreviewTarget::Output);
+        let output_handle = self
+            .state
+            .get_display_handle(PreviewTarget::Output)
+            .unwrap_or_else(|| output_preview.handle_for_size(size));
+        let input_handle = self
+            .state
+            .get_display_handle(PreviewTarget::CompareInput)
+            .unwrap_or(output_handle);
+
+        match self.state.compare_mode {
+            CompareMode::Split => {
+                let handle = self.state.compare_display.as_ref().unwrap_or(output_handle);
+                self.view_interactive(PreviewTarget::Output, handle, size)
+            }
+            CompareMode::SideBySide => row![
+                column![
+                    text("Before").size(12),
+                    self.view_interactive(PreviewTarget::CompareInput, input_handle, size)
+                ]
+                .spacing(4)
+                .align_x(iced::Alignment::Center),
+                column![
+                    text("After").size(12),
+                    self.view_interactive(PreviewTarget::Output, output_handle, size)
+                ]
+                .spacing(4)
+                .align_x(iced::Alignment::Center),
+            ]
+            .into(),
+            CompareMode::Flicker | CompareMode::Off => {
+                let handle = if self.state.flicker_showing_input {
+                    input_handle
+                } else {
+                    output_handle
+                };
+                self.view_interactive(PreviewTarget::Output, handle, size)
+            }
+        }
+    }
+
+    /// Render the compare mode and compared input pickers
+    fn view_compare_controls(&self) -> Element<'_, TextureSplitterMessage> {
+        use crate::widget_helpers::pick_list_style;
+
+        let sources: Vec<CompareSource> = self
+            .state
+            .input_slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.image.is_some())
+            .map(|(index, slot)| CompareSource {
+                index,
+                label: slot.label.clone(),
+            })
+            .collect();
+        let selected_source = sources
+            .iter()
+            .find(|source| source.index == self.state.compare_input)
+            .cloned();
+
+        let mut controls = row![
+            pick_list(
+                &CompareMode::ALL[..],
+                Some(self.state.compare_mode),
+                TextureSplitterMessage::CompareModeSelected,
+            )
+            .text_size(12)
+            .style(pick_list_style)
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+        if self.state.compare_mode != CompareMode::Off {
+            controls = controls.push(
+                pick_list(
+                    sources,
+                    selected_source,
+                    TextureSplitterMessage::CompareInputSelected,
+                )
+                .placeholder("Load an input")
+                .text_size(12)
+                .style(pick_list_style),
+            );
+
+            if self.state.compare_mode == CompareMode::Flicker {
+                let showing = if self.state.flicker_showing_input {
+                    "Before"
+                } else {
+                    "After"
+                };
+                controls = controls.push(text(showing).size(12));
+            }
+        }
+
+        controls.into()
+    }
+
+    /// Render the frame picker for multi-frame inputs and the mipmap toggle
+    fn view_frame_controls(&self) -> Option<Element<'_, TextureSplitterMessage>> {
+        use crate::widget_helpers::pick_list_style;
+
+        let layout = self.state.frame_layout();
+        let has_inputs = self
+            .state
+            .input_slots
+            .iter()
+            .any(|slot| slot.image.is_some());
+        if !has_inputs {
+            return None;
+        }
+
+        let mut controls = row![].spacing(8).align_y(iced::Alignment::Center);
+
+        if layout.is_multi_frame() {
+            let choices = layout.choices();
+            let selected = choices.get(self.state.current_frame()).copied();
+            controls = controls.push(
+                pick_list(choices, selected, TextureSplitterMessage::FrameSelected)
+                    .text_size(12)
+                    .style(pick_list_style),
+            );
+        }
+
+        controls = controls.push(
+            iced::widget::checkbox("Save mipmaps", layout.mipmaps)
+                .on_toggle(TextureSplitterMessage::SaveMipmapsToggled)
+                .size(14)
+                .text_size(12),
+        );
+
+        Some(controls.into())
+    }
+
+    /// Render the alpha interpretation picker shared by every preview
+    fn view_alpha_preview(&self) -> Element<'_, TextureSplitterMessage> {
+        use crate::widget_helpers::pick_list_style;
+
+        pick_list(
+            AlphaPreview::ALL,
+            Some(self.state.preview_alpha),
+            TextureSplitterMessage::AlphaPreviewSelected,
+        )
+        .text_size(12)
+        .style(pick_list_style)
+        .into()
+    }
+
+    /// Render the picker for how float inputs outside 0-1 are read
+    fn view_float_range(&self) -> Element<'_, TextureSplitterMessage> {
+        use crate::widget_helpers::pick_list_style;
+
+        pick_list(
+            FloatRange::ALL,
+            Some(self.float_range),
+            TextureSplitterMessage::FloatRangeSelected,
+        )
+        .text_size(12)
+        .style(pick_list_style)
+        .into()
+    }
+
+    /// Recompute statistics if `changed` is the selected stats preview
+    fn refresh_stats_for(&mut self, changed: PreviewTarget) -> Task<Message> {
+        if self.state.stats_target == Some(changed) {
+            self.refresh_stats()
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Recompute statistics for the selected preview in the background
+    ///
+    /// Results from earlier requests are ignored via the generation counter.
+    fn refresh_stats(&mut self) -> Task<Message> {
+        self.state.stats_generation += 1;
+        let generation = self.state.stats_generation;
+
+        let buffer = match self.state.stats_target {
+            Some(PreviewTarget::Input(idx)) => self
+                .state
+                .input_slot_pixels
+                .get(idx)
+                .and_then(|pixels| pixels.clone()),
+            Some(PreviewTarget::Output) => self
+                .state
+                .output_buffers
+                .get(self.state.current_output_index)
+                .cloned(),
+            Some(PreviewTarget::CompareInput) | None => None,
+        };
+
+        let Some(buffer) = buffer else {
+            self.state.stats = None;
+            return Task::none();
+        };
+
+        Task::perform(
+            async move { Box::new(StatsReport::from_buffer(&buffer)) },
+            move |report| {
+                Message::Main(crate::windows::MainMessage::TextureSplitter(
+                    TextureSplitterMessage::StatsComputed(generation, report),
+                ))
+            },
+        )
+    }
+
+    /// Render the statistics panel for the selected preview
+    fn view_stats_panel(&self) -> Element<'_, TextureSplitterMessage> {
+        use crate::status::Severity;
+        use crate::widget_helpers::{control, pick_list_style};
+
+        let mut sources = Vec::new();
+        if !self.state.output_buffers.is_empty() {
+            sources.push(StatsSource {
+                target: PreviewTarget::Output,
+                label: "Output".to_string(),
+            });
+        }
+        for (idx, slot) in self.state.input_slots.iter().enumerate() {
+            if slot.image.is_some() {
+                sources.push(StatsSource {
+                    target: PreviewTarget::Input(idx),
+                    label: slot.label.clone(),
+                });
+            }
+        }
+
+        let selected = sources
+            .iter()
+            .find(|source| Some(source.target) == self.state.stats_target)
+            .cloned();
+
+        let source_picker = pick_list(
+            sources,
+            selected,
+            TextureSplitterMessage::StatsSourceSelected,
+        )
+        .placeholder("Select a preview")
+        .text_size(12)
+        .style(pick_list_style);
+
+        let mut panel = column![source_picker].spacing(4);
+
+        if let Some(report) = &self.state.stats {
+            let stats = &report.stats;
+
+            panel = panel
+                .push(
+                    iced::widget::image(report.histogram.clone())
+                        .width(Length::Fill)
+                        .height(stats::HISTOGRAM_HEIGHT as f32)
+                        .content_fit(iced::ContentFit::Fill),
+                )
+                .push(text(format!("{} x {}", stats.width, stats.height)).size(11));
+
+            for index in 0..stats.channels.len() {
+                panel = panel.push(text(stats.channel_summary(index)).size(11));
+            }
+
+            panel = panel.push(
+                text(format!(
+                    "Transparent {}  Opaque {}",
+                    stats.transparent_pixels, stats.opaque_pixels
+                ))
+                .size(11),
+            );
+
+            for warning in &stats.warnings {
+                panel = panel.push(text(warning).size(11).color(Severity::Warning.colour()));
+            }
+        }
+
+        control(text("Statistics").size(13).into(), panel.into()).into()
+    }
+
+    /// KTX2 encoder settings, shown while any output is saved as KTX2
+    fn view_ktx2_settings(&self) -> Option<Element<'_, TextureSplitterMessage>> {
+        use crate::widget_helpers::pick_list_style;
+
+        let formats = self.save_formats();
+        let uses_ktx2 = match self.state.output_descriptions.as_slice() {
+            [] => formats.default == ImageFormat::Ktx2,
+            descriptions => descriptions
+                .iter()
+                .any(|description| formats.for_output(description) == ImageFormat::Ktx2),
+        };
+        if !uses_ktx2 {
+            return None;
+        }
+
+        let settings = self.ktx2_settings;
+        let encoding = pick_list(
+            &Ktx2Encoding::ALL[..],
+            Some(settings.encoding),
+            TextureSplitterMessage::Ktx2EncodingSelected,
+        )
+        .text_size(12)
+        .padding([2, 6])
+        .style(pick_list_style);
+
+        let mut zstd = iced::widget::checkbox(
+            "Zstandard",
+            settings.zstd && settings.encoding.supports_zstd(),
+        )
+        .size(14)
+        .text_size(12);
+        if settings.encoding.supports_zstd() {
+            zstd = zstd.on_toggle(TextureSplitterMessage::Ktx2ZstdToggled);
+        }
+
+        let srgb = iced::widget::checkbox("sRGB", settings.srgb)
+            .on_toggle(TextureSplitterMessage::Ktx2SrgbToggled)
+            .size(14)
+            .text_size(12);
+
+        Some(
+            row![text("KTX2:").size(12), encoding, zstd, srgb]
+                .spacing(12)
+                .align_y(iced::Alignment::Center)
+                .into(),
+        )
+    }
+
+    /// Dithering for outputs without their own choice
+    fn view_dither_settings(&self) -> Element<'_, TextureSplitterMessage> {
+        use crate::widget_helpers::pick_list_style;
+
+        let mode = pick_list(
+            &DitherMode::ALL[..],
+            Some(self.dither_mode),
+            TextureSplitterMessage::DitherSelected,
+        )
+        .text_size(12)
+        .padding([2, 6])
+        .style(pick_list_style);
+
+        row![text("Dither 8-bit outputs:").size(12), mode]
+            .spacing(12)
+            .align_y(iced::Alignment::Center)
+            .into()
+    }
+
+    /// Size each output is rendered at, and power-of-two rounding
+    ///
+    /// Returns None until the shader has outputs.
+    fn view_size_settings(&self) -> Option<Element<'_, TextureSplitterMessage>> {
+        use crate::widget_helpers::pick_list_style;
+
+        if self.state.output_descriptions.is_empty() {
+            return None;
+        }
+
+        let sizes = self.render_options().sizes;
+        let mut size_row = row![text("Size:").size(12)]
+            .spacing(12)
+            .align_y(iced::Alignment::Center);
+
+        for description in &self.state.output_descriptions {
+            let size = pick_list(
+                &SizeOverride::ALL[..],
+                Some(sizes.for_output(description)),
+                |size| TextureSplitterMessage::OutputSizeSelected(description.clone(), size),
+            )
+            .text_size(12)
+            .padding([2, 6])
+            .style(pick_list_style);
+
+            size_row = size_row.push(
+                row![text(description.as_str()).size(12), size]
+                    .spacing(6)
+                    .align_y(iced::Alignment::Center),
+            );
+        }
+
+        let power_of_two = iced::widget::checkbox("Power of two", self.power_of_two_sizes)
+            .on_toggle(TextureSplitterMessage::PowerOfTwoToggled)
+            .size(14)
+            .text_size(12);
+
+        Some(size_row.push(power_of_two).wrap().into())
+    }
+
+    /// Render running jobs with their progress and a cancel button
+    ///
+    /// Returns None when nothing is running.
+    fn view_jobs(&self) -> Option<Element<'_, TextureSplitterMessage>> {
+        use iced::widget::Column;
+
+        let jobs = self.state.jobs.jobs();
+        if jobs.is_empty() {
+            return None;
+        }
+
+        let mut list = Column::new().spacing(4);
+        for job in jobs {
+            let progress = match job.step {
+                Some((current, total)) => format!("{} {}/{}", job.stage, current, total),
+                None => job.stage.to_string(),
+            };
+            let cancel_button = button(text(if job.is_cancelling() {
+                "Cancelling..."
+            } else {
+                "Cancel"
+            }))
+            .on_press_maybe(
+                (!job.is_cancelling()).then_some(TextureSplitterMessage::CancelJob(job.id)),
+            )
+            .padding([4, 10])
+            .style(crate::widget_helpers::secondary_button_style);
+
+            list = list.push(
+                row![
+                    column![text(&job.label).size(12), text(progress).size(11)]
+                        .spacing(2)
+                        .width(Length::Fill),
+                    cancel_button,
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        Some(list.into())
+    }
+
+    /// Render the channel toggle buttons for a preview
+    fn view_channel_toggles(&self, target: PreviewTarget) -> Element<'_, TextureSplitterMessage> {
+        use crate::widget_helpers::{primary_button_style, secondary_button_style};
+
+        let selected = self.state.channel_view(target);
+
+        let toggles = ChannelView::ALL.iter().map(|&channel| {
+            button(text(channel.label()).size(11))
+                .on_press(TextureSplitterMessage::ChannelSelected(target, channel))
+                .padding([2, 6])
+                .style(if channel == selected {
+                    primary_button_style
+                } else {
+                    secondary_button_style
+                })
+                .into()
+        });
+
+        row(toggles).spacing(4).into()
+    }
+
+    /// Render at full resolution once parameter edits have settled
+    fn on_parameters_settled(&mut self) -> Task<Message> {
+        self.state.parameter_edit_pending = false;
+
+        if self.state.all_required_slots_filled() {
+            return self.trigger_merge_from_slots();
+        }
+        Task::none()
+    }
+
+    /// Handle GPU processing completion
+    ///
+    /// Updates state with processing results and converts output buffers to
+    /// displayable image handles. Uses generation counter to ignore stale results.
+    fn on_merge_completed(
+        &mut self,
+        result: Result<Vec<ProcessedOutput>, String>,
+        generation: u64,
+    ) -> Task<Message> {
+        if generation != self.state.merge_generation {
+            return Task::none();
+        }
+
+        if let Some(job) = self.state.process_job.take() {
+            self.state.jobs.finish(job);
+        }
+        let mut stats_task = Task::none();
+        let pending_output_index = self.state.pending_output_index.take();
+
+        match result {
+            Ok(outputs) => {
+                let preview = self.state.merge_is_preview;
+                let capped = (!preview).then(|| self.capped_outputs(&outputs)).flatten();
+
+                // Stay on the selected output while previews replace each other
+                let kept_index = (preview || self.state.outputs_are_preview)
+                    .then_some(self.state.current_output_index);
+
+                self.state.set_outputs(outputs);
+                self.state.outputs_are_preview = preview;
+                if let Some(index) = pending_output_index.or(kept_index) {
+                    self.state.current_output_index =
+                        index.min(self.state.outputs.len().saturating_sub(1));
+                }
+                let displays = self.refresh_displays();
+
+                if preview {
+                    self.state.status = StatusMessage::info("Previewing at reduced resolution");
+
+                    // Catch up with values that changed while this rendered
+                    if self.state.preview_stale && self.state.parameter_edit_pending {
+                        return Task::batch([displays, self.trigger_merge(true)]);
+                    }
+                    return displays;
+                }
+
+                stats_task = Task::batch([displays, self.refresh_stats_for(PreviewTarget::Output)]);
+
+                if let Some(capped) = capped {
+                    self.state.status = StatusMessage::warning(format!(
+                        "Processing complete - {capped} by the GPU texture limit"
+                    ));
+                } else if self.state.outputs.len() > 1 {
+                    self.state.status = StatusMessage::success(format!(
+                        "Processing complete - {} outputs generated",
+                        self.state.outputs.len()
+                    ));
+                } else {
+                    self.state.status = StatusMessage::success("Processing complete");
+                }
+            }
+            Err(e) if e == jobs::CANCELLED => {
+                self.state.status = StatusMessage::warning("Processing cancelled");
+            }
+            Err(e) => {
+                self.state.status = StatusMessage::error(format!("Processing failed: {e}"));
+            }
+        }
+
+        stats_task
+    }
+}
+
+/// The dropped path if exactly one file was dropped
+fn single_file(paths: &[PathBuf]) -> Option<PathBuf> {
+    match paths {
+        [path] if path.is_file() => Some(path.clone()),
+        _ => None,
+    }
+}
+
+/// Status warning for a float input whose values were remapped into 0-1
+fn remap_warning(slot_idx: usize, remap: &FloatRemap) -> String {
+    format!("Slot {slot_idx}: {}", remap.warning())
+}
+
+/// File name for an output, derived from its description
+fn description_file_name(description: &str, format: ImageFormat) -> String {
+    format!(
+        "{}.{}",
+        description
+            .to_lowercase()
+            .replace(" ", "_")
+            .replace("/", "_")
+            .replace("\\", "_"),
+        format.extension()
+    )
+}
+
+/// Wait for the flicker interval, then swap the compared sides
+fn schedule_flicker_tick(generation: u64) -> Task<Message> {
+    Task::perform(
+        async move {
+            futures_timer::Delay::new(std::time::Duration::from_millis(
+                compare::FLICKER_INTERVAL_MS,
+            ))
+            .await;
+            generation
+        },
+        |generation| {
+            Message::Main(crate::windows::MainMessage::TextureSplitter(
+                TextureSplitterMessage::FlickerTick(generation),
+            ))
+        },
+    )
+}
+
+/// Preview size for each input slot, based on how many slots are shown
+fn slot_preview_size(slot_count: usize) -> f32 {
+    match slot_count {
+        1 => SLOT_SIZE_SINGLE,
+        2 => SLOT_SIZE_TWO,
+        3 => SLOT_SIZE_THREE,
+        4 => SLOT_SIZE_FOUR,
+        _ => SLOT_SIZE_MANY,
     }
 }
 
@@ -0,0 +1,103 @@
+//! Parallel Module
+//!
+//! Runs CPU-heavy decode and encode work on the shared rayon thread pool.
+//!
+//! Decoding and encoding block for as long as a file takes to read or write,
+//! so they run on the pool set up by `initialize_thread_pool` instead of the
+//! async executor. The files within one call are spread across the pool's
+//! threads, and the caller awaits the combined result.
+
+use crate::components::texture_converter::frames::FramedOutput;
+use crate::components::texture_converter::jobs::{JobProgress, JobStage};
+use crate::components::texture_converter::types::LoadedImage;
+use crate::float_images::FloatRange;
+use crate::porter_image::{PorterImage, SaveOptions};
+use futures::channel::oneshot;
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Run a closure on the thread pool and wait for its result
+pub async fn spawn<T, F>(work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    rayon::spawn(move || {
+        // The receiver is gone if the task awaiting it was dropped
+        let _ = sender.send(work());
+    });
+
+    receiver
+        .await
+        .map_err(|_| "Background task stopped unexpectedly".to_string())
+}
+
+/// Decode image files in parallel, keeping their order
+pub async fn open_images(
+    paths: Vec<PathBuf>,
+    range: FloatRange,
+) -> Result<Vec<PorterImage>, String> {
+    spawn(move || {
+        paths
+            .par_iter()
+            .map(|path| {
+                PorterImage::open(path, range)
+                    .map_err(|e| format!("Failed to load {}: {e}", path.display()))
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })
+    .await?
+}
+
+/// Load images for input slots in parallel, keeping their order
+///
+/// Blocks until every file is loaded, so call it from the thread pool.
+pub fn load_slot_images(
+    files: Vec<(usize, PathBuf)>,
+    range: FloatRange,
+) -> Vec<(usize, Result<LoadedImage, String>)> {
+    files
+        .into_par_iter()
+        .map(|(slot, path)| {
+            let loaded =
+                LoadedImage::open(&path, range).map_err(|e| format!("Failed to load image: {e}"));
+            (slot, loaded)
+        })
+        .collect()
+}
+
+/// Encode and write images in parallel
+///
+/// Each image is written atomically, so a failed or cancelled save never
+/// leaves a partial file behind. Reports an `Encode` step as each file
+/// starts and returns the written paths in the order given.
+pub async fn save_images(
+    images: Vec<(FramedOutput, PathBuf)>,
+    options: SaveOptions,
+    progress: JobProgress,
+) -> Result<Vec<PathBuf>, String> {
+    spawn(move || {
+        let total = images.len();
+        let started = AtomicUsize::new(0);
+
+        images
+            .into_par_iter()
+            .map(|(output, path)| {
+                let current = started.fetch_add(1, Ordering::Relaxed) + 1;
+                progress.step(JobStage::Encode, current, total)?;
+
+                let mut image = output
+                    .into_porter_image()
+                    .map_err(|e| format!("Failed to convert {}: {e}", path.display()))?;
+                image
+                    .save_atomic(&path, &options)
+                    .map_err(|e| format!("Failed to save {}: {e}", path.display()))?;
+
+                Ok(path)
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })
+    .await?
+}
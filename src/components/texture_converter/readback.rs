@@ -0,0 +1,258 @@
+//! Readback Module
+//!
+//! Copying rendered textures back to the CPU.
+//!
+//! Every output of a render is copied into a staging buffer in the same
+//! command encoder, so a single submission renders and copies them all. The
+//! buffers are then mapped together and read once the GPU finishes, rather
+//! than waiting on each output in turn. The wait polls the device without
+//! blocking and yields between polls, so the executor thread stays free while
+//! the GPU works. Staging buffers come from a pool and
+//! are returned to it afterwards, so repeated renders of the same size don't
+//! allocate. Dithered outputs are read as 16-bit floats and quantised to
+//! RGBA8 while their rows are copied.
+
+use crate::components::texture_converter::dither::DitherMode;
+use crate::components::texture_converter::gpu_processor::GpuResult;
+use crate::porter_image::ImageBuffer;
+use futures::FutureExt;
+use std::time::Duration;
+
+/// Most staging memory kept in the pool between renders
+const MAX_POOLED_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Time between device polls while waiting for buffers to map
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Reusable staging buffers for readback
+#[derive(Debug, Default)]
+pub struct StagingPool {
+    free: Vec<wgpu::Buffer>,
+}
+
+impl StagingPool {
+    /// Take a buffer of at least `size` bytes, creating one if none is free
+    ///
+    /// The smallest buffer that fits is used, so large buffers stay free for
+    /// large renders.
+    fn acquire(&mut self, device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+        let best = self
+            .free
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| buffer.size() >= size)
+            .min_by_key(|(_, buffer)| buffer.size())
+            .map(|(idx, _)| idx);
+
+        match best {
+            Some(idx) => self.free.swap_remove(idx),
+            None => device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Staging Buffer"),
+                size,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+        }
+    }
+
+    /// Return a buffer to the pool
+    ///
+    /// When the pool is over its budget the largest buffers are dropped.
+    fn release(&mut self, buffer: wgpu::Buffer) {
+        self.free.push(buffer);
+        self.free.sort_by_key(|buffer| buffer.size());
+
+        while self.free.iter().map(wgpu::Buffer::size).sum::<u64>() > MAX_POOLED_BYTES {
+            self.free.pop();
+        }
+    }
+}
+
+/// A texture copy recorded into an encoder, waiting to be read
+#[derive(Debug)]
+pub struct PendingReadback {
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    bytes_per_pixel: u32,
+    padded_bytes_per_row: u32,
+    dither: DitherMode,
+    /// Position of the copied texture in the full image
+    origin: (u32, u32),
+}
+
+impl PendingReadback {
+    /// Bytes of the staging buffer the copy fills
+    fn used_size(&self) -> u64 {
+        self.padded_bytes_per_row as u64 * self.height as u64
+    }
+}
+
+/// Record a copy of a rendered texture into a pooled staging buffer
+///
+/// The texture must be in the dither mode's render format. `origin` places
+/// it in the full image, so dither patterns continue across tiles.
+pub fn copy_to_staging(
+    device: &wgpu::Device,
+    pool: &mut StagingPool,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+    texture_size: wgpu::Extent3d,
+    dither: DitherMode,
+    origin: (u32, u32),
+) -> PendingReadback {
+    let width = texture_size.width;
+    let height = texture_size.height;
+    let bytes_per_pixel = texture.format().block_copy_size(None).unwrap_or(4);
+    let padded_bytes_per_row = {
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        (bytes_per_pixel * width).div_ceil(align) * align
+    };
+
+    let buffer = pool.acquire(device, padded_bytes_per_row as u64 * height as u64);
+
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        texture_size,
+    );
+
+    PendingReadback {
+        buffer,
+        width,
+        height,
+        bytes_per_pixel,
+        padded_bytes_per_row,
+        dither,
+        origin,
+    }
+}
+
+/// Submit an encoder and read back every copy recorded into it
+///
+/// All buffers are mapped at once and awaited together. Rows are copied
+/// straight from the mapped buffers into the returned images, in the order
+/// the copies were recorded.
+pub async fn read_back(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    pool: &mut StagingPool,
+    encoder: wgpu::CommandEncoder,
+    pending: Vec<PendingReadback>,
+) -> GpuResult<Vec<ImageBuffer>> {
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let receivers: Vec<_> = pending
+        .iter()
+        .map(|readback| {
+            let (sender, receiver) = futures::channel::oneshot::channel();
+            readback.buffer.slice(..readback.used_size()).map_async(
+                wgpu::MapMode::Read,
+                move |result| {
+                    let _ = sender.send(result);
+                },
+            );
+            receiver
+        })
+        .collect();
+
+    // Map callbacks only run while the device is polled
+    let mut maps = futures::future::join_all(receivers);
+    let results = loop {
+        let _ = device.poll(wgpu::PollType::Poll);
+        if let Some(results) = (&mut maps).now_or_never() {
+            break results;
+        }
+        futures_timer::Delay::new(POLL_INTERVAL).await;
+    };
+
+    let mut images = Vec::with_capacity(pending.len());
+    let mut error = None;
+
+    for (readback, result) in pending.into_iter().zip(results) {
+        let mapped = match result {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                error.get_or_insert_with(|| format!("Buffer mapping failed: {e}"));
+                false
+            }
+            Err(e) => {
+                error.get_or_insert_with(|| format!("Channel error: {e}"));
+                false
+            }
+        };
+
+        // Every buffer goes back to the pool, even after an error
+        if mapped && error.is_none() {
+            images.push(copy_rows(&readback));
+        }
+        if mapped {
+            readback.buffer.unmap();
+        }
+        pool.release(readback.buffer);
+    }
+
+    match error {
+        Some(e) => Err(e),
+        None => images.into_iter().collect(),
+    }
+}
+
+/// Copy a mapped staging buffer into an RGBA8 image, dropping the row
+/// padding and quantising dithered outputs
+fn copy_rows(readback: &PendingReadback) -> GpuResult<ImageBuffer> {
+    let bytes_per_row = (readback.bytes_per_pixel * readback.width) as usize;
+    let padded_bytes_per_row = readback.padded_bytes_per_row as usize;
+    let data = readback
+        .buffer
+        .slice(..readback.used_size())
+        .get_mapped_range();
+
+    let mut rgba_data = Vec::with_capacity(4 * (readback.width * readback.height) as usize);
+    match readback.dither {
+        DitherMode::Off if padded_bytes_per_row == bytes_per_row => {
+            rgba_data.extend_from_slice(&data);
+        }
+        DitherMode::Off => {
+            for row in data.chunks_exact(padded_bytes_per_row) {
+                rgba_data.extend_from_slice(&row[..bytes_per_row]);
+            }
+        }
+        mode => {
+            let (origin_x, origin_y) = readback.origin;
+            for (y, row) in data.chunks_exact(padded_bytes_per_row).enumerate() {
+                let y = origin_y + y as u32;
+                for (x, texel) in row[..bytes_per_row].chunks_exact(8).enumerate() {
+                    let x = origin_x + x as u32;
+                    rgba_data.extend(texel.chunks_exact(2).map(|half| {
+                        mode.quantize(f16_to_f32(u16::from_le_bytes([half[0], half[1]])), x, y)
+                    }));
+                }
+            }
+        }
+    }
+
+    ImageBuffer::from_raw(readback.width, readback.height, rgba_data)
+        .ok_or_else(|| "Failed to create ImageBuffer".to_string())
+}
+
+/// Decode an IEEE 754 half-precision float
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as f32;
+
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent as i32 - 15),
+    }
+}
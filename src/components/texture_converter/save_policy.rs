@@ -0,0 +1,141 @@
+//! Save Policy Module
+//!
+//! Decides what happens when a save would replace an existing file.
+//!
+//! Conflicts are resolved before anything is written: every destination of a
+//! save is checked up front and, with the `Ask` policy, one prompt lists all
+//! of the conflicting files. The answer then applies to the whole save.
+
+use rfd::{AsyncMessageDialog, MessageButtons, MessageDialogResult, MessageLevel};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Number of conflicting files listed by name in the prompt
+const MAX_LISTED_CONFLICTS: usize = 10;
+
+/// Button labels for the conflict prompt
+const OVERWRITE_LABEL: &str = "Overwrite";
+const SKIP_LABEL: &str = "Skip existing";
+const CANCEL_LABEL: &str = "Cancel";
+
+/// How saving treats files that already exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SavePolicy {
+    #[default]
+    Ask,
+    Overwrite,
+    Skip,
+    AutoIncrement,
+}
+
+impl SavePolicy {
+    /// All save policies
+    pub const ALL: [SavePolicy; 4] = [
+        SavePolicy::Ask,
+        SavePolicy::Overwrite,
+        SavePolicy::Skip,
+        SavePolicy::AutoIncrement,
+    ];
+
+    /// Turn `Ask` into a concrete policy for a set of destinations
+    ///
+    /// Prompts once if any of the paths exist. Returns None if the user
+    /// cancels the save.
+    pub async fn resolve(self, paths: &[PathBuf]) -> Option<SavePolicy> {
+        if self != SavePolicy::Ask {
+            return Some(self);
+        }
+
+        let conflicts: Vec<&PathBuf> = paths.iter().filter(|path| path.exists()).collect();
+        if conflicts.is_empty() {
+            return Some(SavePolicy::Overwrite);
+        }
+
+        let mut description = format!(
+            "{} file{} already exist{}:\n",
+            conflicts.len(),
+            if conflicts.len() == 1 { "" } else { "s" },
+            if conflicts.len() == 1 { "s" } else { "" }
+        );
+        for path in conflicts.iter().take(MAX_LISTED_CONFLICTS) {
+            description.push_str(&format!("\n{}", path.display()));
+        }
+        if conflicts.len() > MAX_LISTED_CONFLICTS {
+            description.push_str(&format!(
+                "\n...and {} more",
+                conflicts.len() - MAX_LISTED_CONFLICTS
+            ));
+        }
+
+        let result = AsyncMessageDialog::new()
+            .set_level(MessageLevel::Warning)
+            .set_title("Replace existing files?")
+            .set_description(description)
+            .set_buttons(MessageButtons::YesNoCancelCustom(
+                OVERWRITE_LABEL.to_string(),
+                SKIP_LABEL.to_string(),
+                CANCEL_LABEL.to_string(),
+            ))
+            .show()
+            .await;
+
+        // Some platforms report custom buttons by label, others by position
+        match result {
+            MessageDialogResult::Yes => Some(SavePolicy::Overwrite),
+            MessageDialogResult::No => Some(SavePolicy::Skip),
+            MessageDialogResult::Custom(label) if label == OVERWRITE_LABEL => {
+                Some(SavePolicy::Overwrite)
+            }
+            MessageDialogResult::Custom(label) if label == SKIP_LABEL => Some(SavePolicy::Skip),
+            _ => None,
+        }
+    }
+
+    /// Where to write a file under this policy, or None to skip it
+    ///
+    /// `Ask` should be resolved first; it is treated like `Overwrite` here.
+    pub fn destination(self, path: PathBuf) -> Option<PathBuf> {
+        match self {
+            SavePolicy::Skip if path.exists() => None,
+            SavePolicy::AutoIncrement => Some(next_free_path(path)),
+            _ => Some(path),
+        }
+    }
+}
+
+impl fmt::Display for SavePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SavePolicy::Ask => "Ask if exists",
+            SavePolicy::Overwrite => "Overwrite",
+            SavePolicy::Skip => "Skip existing",
+            SavePolicy::AutoIncrement => "Auto-number",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// First path of `name.ext`, `name_1.ext`, `name_2.ext`... that doesn't exist
+fn next_free_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut n = 1;
+    loop {
+        let candidate = path.with_file_name(format!("{stem}_{n}{extension}"));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
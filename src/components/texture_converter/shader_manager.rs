@@ -29,6 +29,12 @@ pub async fn load_shaders() -> ShaderResult<(Vec<ShaderConfig>, usize)> {
     // Initialize wgpu for shader validation
     let (device, _queue) = initialize_gpu_device().await?;
 
+    // Tiles are sized for the device that renders, not the validation device
+    let max_dimension = porter_gpu::gpu_instance()
+        .device()
+        .limits()
+        .max_texture_dimension_2d;
+
     // Find the shaders directory
     let shaders_dir = find_shaders_directory()?;
     tracing::info!("Loading shaders from: {}", shaders_dir.display());
@@ -45,7 +51,7 @@ pub async fn load_shaders() -> ShaderResult<(Vec<ShaderConfig>, usize)> {
     let mut failed_count = 0;
 
     for shader_path in shader_files {
-        match load_and_validate_shader(&shader_path, &device).await {
+        match load_and_validate_shader(&shader_path, &device, max_dimension).await {
             Ok(shader) => loaded_shaders.push(shader),
             Err(e) => {
                 tracing::error!("Failed to load shader {:?}: {}", shader_path, e);
@@ -154,10 +160,12 @@ fn discover_shader_files(shaders_dir: &Path) -> ShaderResult<Vec<PathBuf>> {
 /// Performs the following steps:
 /// 1. Load and parse config.toml
 /// 2. Validate WGSL shader code
-/// 3. Validate configuration consistency
+/// 3. Validate configuration consistency against the rendering device's
+///    texture limit
 async fn load_and_validate_shader(
     shader_dir: &Path,
     device: &wgpu::Device,
+    max_dimension: u32,
 ) -> ShaderResult<ShaderConfig> {
     let shader_wgsl = shader_dir.join("shader.wgsl");
     let config_toml = shader_dir.join("config.toml");
@@ -176,7 +184,7 @@ async fn load_and_validate_shader(
     validate_shader_code(&shader_wgsl, device)?;
 
     // Validate config consistency
-    validate_shader_config(&shader_config)?;
+    validate_shader_config(&shader_config, max_dimension)?;
 
     tracing::info!("Successfully loaded shader: {}", shader_config.shader.name);
 
@@ -210,8 +218,10 @@ fn validate_shader_code(shader_path: &Path, device: &wgpu::Device) -> ShaderResu
 /// Checks that:
 /// - At least one output is defined
 /// - All output entry points and suffixes are non-empty
+/// - Output scales are positive and output sizes non-zero
 /// - Parameter ranges are valid (min <= default <= max)
-fn validate_shader_config(config: &ShaderConfig) -> ShaderResult<()> {
+/// - The tile apron leaves room for an interior region within `max_dimension`
+fn validate_shader_config(config: &ShaderConfig, max_dimension: u32) -> ShaderResult<()> {
     // Check that shader has at least one output
     if config.outputs.is_empty() {
         return Err("Shader must have at least one output".to_string());
@@ -225,6 +235,21 @@ fn validate_shader_config(config: &ShaderConfig) -> ShaderResult<()> {
         if output.suffix.is_empty() {
             return Err("Output suffix cannot be empty".to_string());
         }
+        if output
+            .scale
+            .is_some_and(|scale| !scale.is_finite() || scale <= 0.0)
+        {
+            return Err(format!(
+                "Output '{}' scale must be greater than zero",
+                output.description
+            ));
+        }
+        if output.width == Some(0) || output.height == Some(0) {
+            return Err(format!(
+                "Output '{}' width and height must be greater than zero",
+                output.description
+            ));
+        }
     }
 
     // Check that all parameters have valid ranges
@@ -243,5 +268,13 @@ fn validate_shader_config(config: &ShaderConfig) -> ShaderResult<()> {
         }
     }
 
+    // The apron is rendered on both sides of each tile
+    if config.shader.tile_apron.saturating_mul(2) >= max_dimension {
+        return Err(format!(
+            "Tile apron ({}) must be less than half the texture limit ({})",
+            config.shader.tile_apron, max_dimension
+        ));
+    }
+
     Ok(())
 }
@@ -0,0 +1,124 @@
+//! Sizing Module
+//!
+//! Output sizes relative to the inputs.
+//!
+//! Outputs render at the size of the first input unless their config asks
+//! for a `scale`, an absolute `width` and/or `height`, or rounding to a power
+//! of two. A size picked in the UI replaces the configured scale and size for
+//! that output, and rounding can be forced for every output at once. Shaders
+//! sample by UV, so they adapt to any output size; the built-in uniforms
+//! report each output's own resolution and texel size.
+
+use crate::components::texture_converter::OutputConfig;
+use std::collections::HashMap;
+
+/// Size picked for an output in the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeOverride {
+    /// Follow the output's config
+    #[default]
+    Configured,
+    Full,
+    Half,
+    Quarter,
+    Eighth,
+}
+
+impl SizeOverride {
+    /// All sizes, in picker order
+    pub const ALL: [SizeOverride; 5] = [
+        SizeOverride::Configured,
+        SizeOverride::Full,
+        SizeOverride::Half,
+        SizeOverride::Quarter,
+        SizeOverride::Eighth,
+    ];
+
+    /// Scale relative to the first input, or None to follow the config
+    fn scale(&self) -> Option<f32> {
+        match self {
+            SizeOverride::Configured => None,
+            SizeOverride::Full => Some(1.0),
+            SizeOverride::Half => Some(0.5),
+            SizeOverride::Quarter => Some(0.25),
+            SizeOverride::Eighth => Some(0.125),
+        }
+    }
+}
+
+impl std::fmt::Display for SizeOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            SizeOverride::Configured => "Config size",
+            SizeOverride::Full => "Full size",
+            SizeOverride::Half => "1/2 size",
+            SizeOverride::Quarter => "1/4 size",
+            SizeOverride::Eighth => "1/8 size",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Output sizes chosen in the UI
+#[derive(Debug, Clone, Default)]
+pub struct OutputSizes {
+    /// Per-output sizes keyed by output description
+    pub overrides: HashMap<String, SizeOverride>,
+    /// Round every output to a power of two, whatever its config says
+    pub power_of_two: bool,
+}
+
+impl OutputSizes {
+    /// Size picked for an output
+    pub fn for_output(&self, description: &str) -> SizeOverride {
+        self.overrides.get(description).copied().unwrap_or_default()
+    }
+
+    /// Size an output renders at, given the size of the first input
+    pub fn output_size(&self, output: &OutputConfig, base: (u32, u32)) -> (u32, u32) {
+        let (width, height) = match self.for_output(&output.description).scale() {
+            Some(scale) => scaled(base, scale),
+            None => configured_size(output, base),
+        };
+
+        if self.power_of_two || output.power_of_two {
+            (nearest_power_of_two(width), nearest_power_of_two(height))
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// Size from an output's config
+///
+/// A lone `width` or `height` keeps the first input's aspect ratio.
+fn configured_size(output: &OutputConfig, (width, height): (u32, u32)) -> (u32, u32) {
+    let aspect = |size: u32, from: u32, to: u32| {
+        ((size as u64 * to as u64 + from as u64 / 2) / from.max(1) as u64).max(1) as u32
+    };
+
+    match (output.width, output.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, aspect(w, width, height)),
+        (None, Some(h)) => (aspect(h, height, width), h),
+        (None, None) => scaled((width, height), output.scale.unwrap_or(1.0)),
+    }
+}
+
+/// Scale a size, keeping at least one texel on each side
+fn scaled((width, height): (u32, u32), scale: f32) -> (u32, u32) {
+    let scale = |size: u32| ((size as f32 * scale).round() as u32).max(1);
+    (scale(width), scale(height))
+}
+
+/// Closest power of two, rounding up on ties
+fn nearest_power_of_two(size: u32) -> u32 {
+    let size = size.clamp(1, 1 << 31);
+    let up = size.next_power_of_two();
+    let down = up / 2;
+    if down > 0 && size - down < up - size {
+        down
+    } else {
+        up
+    }
+}
@@ -0,0 +1,89 @@
+//! Slot Matching Module
+//!
+//! Assigns dropped files to input slots by file name.
+//!
+//! Each input declares a `suffix` (and optional `aliases`) that its textures
+//! end with, e.g. `wall_n.png` for `_n`. A dropped file goes to the slot whose
+//! suffix it ends with, and files in the same folder that share its base name
+//! fill the other slots: dropping `wall_n.png` also picks up `wall_g.png` and
+//! `wall_o.png`.
+
+use crate::components::texture_converter::InputConfig;
+use crate::porter_image::is_supported_image;
+use std::path::{Path, PathBuf};
+
+/// A file matched to an input slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotMatch {
+    pub slot: usize,
+    /// File stem with the matched suffix removed
+    pub base: String,
+}
+
+/// Find the slot a file belongs to
+///
+/// Matching is case-insensitive. When several suffixes match (`_g` and
+/// `_cs_g`, say) the longest one wins, so more specific inputs take priority.
+pub fn match_slot(inputs: &[InputConfig], path: &Path) -> Option<SlotMatch> {
+    let stem = path.file_stem()?.to_str()?;
+    let lower_stem = stem.to_lowercase();
+
+    inputs
+        .iter()
+        .enumerate()
+        .flat_map(|(slot, input)| {
+            std::iter::once(&input.suffix)
+                .chain(&input.aliases)
+                .map(move |suffix| (slot, suffix.to_lowercase()))
+        })
+        .filter(|(_, suffix)| !suffix.is_empty() && lower_stem.len() > suffix.len())
+        .filter(|(_, suffix)| lower_stem.ends_with(suffix.as_str()))
+        .max_by_key(|(_, suffix)| suffix.len())
+        .map(|(slot, suffix)| {
+            let base_chars = stem.chars().count().saturating_sub(suffix.chars().count());
+            SlotMatch {
+                slot,
+                base: stem.chars().take(base_chars).collect(),
+            }
+        })
+}
+
+/// Find files next to `path` that belong to the other slots
+///
+/// Base names are compared case-insensitively. Only slots in `wanted` are
+/// filled. Files are visited in name order, so the same folder always
+/// produces the same assignment.
+pub fn find_siblings(
+    inputs: &[InputConfig],
+    path: &Path,
+    base: &str,
+    wanted: &[usize],
+) -> Vec<(usize, PathBuf)> {
+    let Some(Ok(entries)) = path.parent().map(std::fs::read_dir) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|candidate| candidate != path && candidate.is_file())
+        .filter(|candidate| is_supported_image(candidate))
+        .collect();
+    candidates.sort();
+
+    let mut siblings: Vec<(usize, PathBuf)> = Vec::new();
+
+    for candidate in candidates {
+        let Some(found) = match_slot(inputs, &candidate) else {
+            continue;
+        };
+
+        if found.base.to_lowercase() == base.to_lowercase()
+            && wanted.contains(&found.slot)
+            && !siblings.iter().any(|(slot, _)| *slot == found.slot)
+        {
+            siblings.push((found.slot, candidate));
+        }
+    }
+
+    siblings
+}
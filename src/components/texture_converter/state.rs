@@ -10,22 +10,41 @@
 //! work during UI rendering.
 
 use crate::components::droppable_image_slot::DroppableImageSlot;
-use crate::components::texture_converter::{ShaderConfig, ShaderParameter};
+use crate::components::texture_converter::alpha::{AlphaMode, AlphaPreview};
+use crate::components::texture_converter::batch::BatchQueue;
+use crate::components::texture_converter::channels::ChannelView;
+use crate::components::texture_converter::compare::CompareMode;
+use crate::components::texture_converter::displays::DisplayScope;
+use crate::components::texture_converter::dither::DitherMode;
+use crate::components::texture_converter::frames::FrameLayout;
+use crate::components::texture_converter::history::{History, SlotSnapshot, Snapshot};
+use crate::components::texture_converter::jobs::JobQueue;
+use crate::components::texture_converter::sizing::SizeOverride;
+use crate::components::texture_converter::stats::StatsReport;
+use crate::components::texture_converter::viewport::{PreviewTarget, ViewTransform};
+use crate::components::texture_converter::{
+    ImageFormat, ProcessedOutput, ShaderConfig, ShaderParameter,
+};
+use crate::float_images::ExrLayers;
 use crate::porter_image::ImageBuffer;
+use crate::preview::PreviewPyramid;
 use crate::status::StatusMessage;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// State for the texture converter component with cached image handles
 ///
 /// This struct maintains all component state including:
 /// - Available shaders and current selection
 /// - Parameter values for each shader
-/// - Input slots with cached image handles (performance optimization)
-/// - Output buffers and display handles
+/// - Input slots with cached preview pyramids (performance optimization)
+/// - Output buffers and display previews
 /// - Debouncing counters for parameter changes
 pub struct TextureConverterState {
     pub status: StatusMessage,
-    pub processing: bool,
+    /// Job rendering the current outputs
+    pub process_job: Option<u64>,
     pub shaders: Vec<ShaderConfig>,
     pub selected_shader: Option<String>,
     pub shaders_loading: bool,
@@ -36,20 +55,102 @@ pub struct TextureConverterState {
     // Input slots for all shaders
     pub input_slots: Vec<DroppableImageSlot>,
 
-    // Cached handles for input slots to avoid regenerating every frame
-    pub input_slot_handles: Vec<Option<iced::widget::image::Handle>>,
+    // Cached previews for input slots to avoid regenerating every frame
+    pub input_slot_previews: Vec<Option<PreviewPyramid>>,
+    pub input_slot_pixels: Vec<Option<Arc<ImageBuffer>>>,
     pub input_slot_generations: Vec<u64>,
+    /// Last generation given to a slot; never reset, so generations can key
+    /// the GPU texture cache
+    pub last_input_generation: u64,
+    /// File and layers of slots loaded from multi-layer EXRs
+    pub input_slot_layers: Vec<Option<(PathBuf, ExrLayers)>>,
 
     // Outputs for preview (multiple outputs per shader)
-    pub outputs: Vec<iced::widget::image::Handle>,
-    pub output_buffers: Vec<ImageBuffer>,
+    pub outputs: Vec<PreviewPyramid>,
+    /// Shared so statistics and saves can hold one without copying it
+    pub output_buffers: Vec<Arc<ImageBuffer>>,
     pub output_descriptions: Vec<String>,
     pub current_output_index: usize,
     pub merge_generation: u64,
-    pub is_saving: bool,
+    /// Job saving outputs
+    pub save_job: Option<u64>,
 
     // Debouncing for parameter changes
     pub parameter_debounce_generation: u64,
+
+    // Low-resolution previews rendered while a slider is dragged
+    /// Whether the running merge is a preview
+    pub merge_is_preview: bool,
+    /// A parameter changed while a preview was rendering
+    pub preview_stale: bool,
+    /// The outputs are a preview and can't be saved
+    pub outputs_are_preview: bool,
+
+    // Zoom and pan shared by all previews
+    pub view: ViewTransform,
+    pub hovered_preview: Option<(PreviewTarget, iced::Point)>,
+    pub dragging_preview: bool,
+
+    // Channel shown by each preview, with the rendered display handles
+    pub input_channels: Vec<ChannelView>,
+    pub output_channel: ChannelView,
+    /// Alpha interpretation shared by every preview
+    pub preview_alpha: AlphaPreview,
+    pub input_displays: Vec<Option<iced::widget::image::Handle>>,
+    pub output_display: Option<iced::widget::image::Handle>,
+    /// A display render is running on the thread pool
+    pub displays_rendering: bool,
+    /// Refresh asked for while a render was running
+    pub pending_displays: Option<DisplayScope>,
+
+    // Before/after comparison in the output area
+    pub compare_mode: CompareMode,
+    pub compare_input: usize,
+    pub split_position: f32,
+    pub dragging_split: bool,
+    pub flicker_showing_input: bool,
+    pub flicker_generation: u64,
+    pub compare_input_display: Option<iced::widget::image::Handle>,
+    pub compare_display: Option<iced::widget::image::Handle>,
+
+    // Statistics for the selected preview
+    pub stats_target: Option<PreviewTarget>,
+    pub stats: Option<StatsReport>,
+    pub stats_generation: u64,
+
+    // Undo/redo, with a burst of parameter edits recorded as one step
+    pub history: History,
+    pub parameter_edit_pending: bool,
+    pub pending_output_index: Option<usize>,
+
+    // Input slot targeted by keyboard shortcuts
+    pub focused_slot: Option<usize>,
+
+    // Background work with progress and cancellation
+    pub jobs: JobQueue,
+
+    // Dropped materials waiting to be processed as a batch
+    pub batch: Option<BatchQueue>,
+
+    // Base name of the loaded textures, used to name saved outputs
+    pub material_name: Option<String>,
+
+    // Outputs left out of Save All, by description
+    pub skipped_outputs: HashSet<String>,
+
+    // Formats chosen for single outputs, by description
+    pub output_formats: HashMap<String, ImageFormat>,
+
+    // Dithering chosen for single outputs, by description
+    pub output_dithers: HashMap<String, DitherMode>,
+
+    // Sizes chosen for single outputs, by description
+    pub output_sizes: HashMap<String, SizeOverride>,
+
+    // Frame of cubemap and array inputs shown in the previews
+    pub selected_frame: usize,
+    // Whether saved outputs get a mip chain; None follows the inputs
+    pub save_mipmaps: Option<bool>,
 }
 
 impl TextureConverterState {
@@ -59,21 +160,61 @@ impl TextureConverterState {
     pub fn new() -> Self {
         Self {
             status: StatusMessage::info("Ready. Select a shader and load images."),
-            processing: false,
+            process_job: None,
             shaders: Vec::new(),
             selected_shader: None,
             shaders_loading: false,
             parameter_values: HashMap::new(),
             input_slots: Vec::new(),
-            input_slot_handles: Vec::new(),
+            input_slot_previews: Vec::new(),
+            input_slot_pixels: Vec::new(),
             input_slot_generations: Vec::new(),
+            last_input_generation: 0,
+            input_slot_layers: Vec::new(),
             outputs: Vec::new(),
             output_buffers: Vec::new(),
             output_descriptions: Vec::new(),
             current_output_index: 0,
             merge_generation: 0,
-            is_saving: false,
+            save_job: None,
             parameter_debounce_generation: 0,
+            merge_is_preview: false,
+            preview_stale: false,
+            outputs_are_preview: false,
+            view: ViewTransform::default(),
+            hovered_preview: None,
+            dragging_preview: false,
+            input_channels: Vec::new(),
+            output_channel: ChannelView::default(),
+            preview_alpha: AlphaPreview::default(),
+            input_displays: Vec::new(),
+            output_display: None,
+            displays_rendering: false,
+            pending_displays: None,
+            compare_mode: CompareMode::default(),
+            compare_input: 0,
+            split_position: 0.5,
+            dragging_split: false,
+            flicker_showing_input: false,
+            flicker_generation: 0,
+            compare_input_display: None,
+            compare_display: None,
+            stats_target: None,
+            stats: None,
+            stats_generation: 0,
+            history: History::default(),
+            parameter_edit_pending: false,
+            pending_output_index: None,
+            focused_slot: None,
+            jobs: JobQueue::default(),
+            batch: None,
+            material_name: None,
+            skipped_outputs: HashSet::new(),
+            output_formats: HashMap::new(),
+            output_dithers: HashMap::new(),
+            output_sizes: HashMap::new(),
+            selected_frame: 0,
+            save_mipmaps: None,
         }
     }
 
@@ -112,17 +253,34 @@ impl TextureConverterState {
     /// Initialize input slots based on shader configuration
     ///
     /// Clears existing slots and creates new ones for each shader input.
-    /// Also clears all cached handles and generation counters.
+    /// Also clears all cached previews and generation counters.
     pub fn initialize_input_slots(&mut self, shader: &ShaderConfig) {
         self.input_slots.clear();
-        self.input_slot_handles.clear();
+        self.input_slot_previews.clear();
+        self.input_slot_pixels.clear();
         self.input_slot_generations.clear();
+        self.input_slot_layers.clear();
+        self.input_displays.clear();
+        self.input_channels.clear();
+        self.compare_input = 0;
+        self.compare_input_display = None;
+        self.compare_display = None;
+        self.material_name = None;
+
+        if matches!(self.stats_target, Some(PreviewTarget::Input(_))) {
+            self.stats_target = None;
+            self.stats = None;
+        }
 
         for input_config in &shader.inputs {
             self.input_slots
                 .push(DroppableImageSlot::new(&input_config.description));
-            self.input_slot_handles.push(None);
+            self.input_slot_previews.push(None);
+            self.input_slot_pixels.push(None);
+            self.input_displays.push(None);
+            self.input_channels.push(ChannelView::default());
             self.input_slot_generations.push(0);
+            self.input_slot_layers.push(None);
         }
     }
 
@@ -141,52 +299,257 @@ impl TextureConverterState {
         }
     }
 
-    /// Update cached handle for an input slot
+    /// Update cached preview for an input slot
     ///
-    /// Regenerates the image handle for the specified slot and stores it
-    /// in the cache. This should be called when an image is loaded or changed.
-    /// Increments the generation counter for the slot.
-    pub fn update_input_slot_handle(&mut self, slot_idx: usize) {
+    /// Stores the preview pyramid and RGBA8 pixels built alongside the
+    /// slot's image. This should be called when an image is loaded or changed.
+    /// Gives the slot a new generation, unique across all slots and clears.
+    pub fn update_input_slot_preview(
+        &mut self,
+        slot_idx: usize,
+        preview: Option<PreviewPyramid>,
+        pixels: Option<Arc<ImageBuffer>>,
+    ) {
         if slot_idx >= self.input_slots.len() {
             return;
         }
 
-        if let Some(img) = &self.input_slots[slot_idx].image {
-            let (w, h) = img.dimensions();
-            if w > 0 && h > 0 && w <= 8192 && h <= 8192 {
-                let mut img_clone = (**img).clone();
-                let handle = crate::core_logic::image_to_handle(&mut img_clone);
+        // Ensure vectors are large enough
+        while self.input_slot_previews.len() <= slot_idx {
+            self.input_slot_previews.push(None);
+        }
+        while self.input_slot_pixels.len() <= slot_idx {
+            self.input_slot_pixels.push(None);
+        }
+        while self.input_slot_generations.len() <= slot_idx {
+            self.input_slot_generations.push(0);
+        }
+
+        if self.input_slots[slot_idx].image.is_some() {
+            self.input_slot_previews[slot_idx] = preview;
+            self.input_slot_pixels[slot_idx] = pixels;
+            self.last_input_generation += 1;
+            self.input_slot_generations[slot_idx] = self.last_input_generation;
+        } else {
+            // Clear the preview if no image
+            self.input_slot_previews[slot_idx] = None;
+            self.input_slot_pixels[slot_idx] = None;
+        }
+    }
 
-                // Ensure vectors are large enough
-                while self.input_slot_handles.len() <= slot_idx {
-                    self.input_slot_handles.push(None);
-                }
-                while self.input_slot_generations.len() <= slot_idx {
-                    self.input_slot_generations.push(0);
-                }
+    /// Frame layout of the loaded inputs, with the mipmap choice applied
+    pub fn frame_layout(&self) -> FrameLayout {
+        let images: Vec<_> = self
+            .input_slots
+            .iter()
+            .map(|slot| slot.image.clone())
+            .collect();
 
-                self.input_slot_handles[slot_idx] = Some(handle);
-                self.input_slot_generations[slot_idx] += 1;
+        let mut layout = FrameLayout::of(&images);
+        layout.mipmaps = self.save_mipmaps.unwrap_or(layout.mipmaps);
+        layout
+    }
+
+    /// Frame to render, kept within the loaded inputs' frames
+    pub fn current_frame(&self) -> usize {
+        self.selected_frame.min(self.frame_layout().count - 1)
+    }
+
+    /// Show another frame of the multi-frame inputs
+    ///
+    /// Multi-frame slots get new generations, since the texture they upload
+    /// changes with the frame.
+    pub fn select_frame(&mut self, frame: usize) {
+        self.selected_frame = frame;
+
+        for (idx, slot) in self.input_slots.iter().enumerate() {
+            let multi_frame = slot
+                .image
+                .as_ref()
+                .is_some_and(|image| image.frame_count() > 1);
+            if multi_frame && idx < self.input_slot_generations.len() {
+                self.last_input_generation += 1;
+                self.input_slot_generations[idx] = self.last_input_generation;
             }
-        } else {
-            // Clear the handle if no image
-            if slot_idx < self.input_slot_handles.len() {
-                self.input_slot_handles[slot_idx] = None;
+        }
+    }
+
+    /// Record the EXR layers a slot's file offers
+    ///
+    /// Only files with more than one layer are kept, since there is nothing
+    /// to choose between otherwise.
+    pub fn set_input_slot_layers(&mut self, slot_idx: usize, layers: Option<(PathBuf, ExrLayers)>) {
+        if slot_idx >= self.input_slots.len() {
+            return;
+        }
+
+        if self.input_slot_layers.len() <= slot_idx {
+            self.input_slot_layers.resize(slot_idx + 1, None);
+        }
+        self.input_slot_layers[slot_idx] = layers.filter(|(_, layers)| layers.names.len() > 1);
+    }
+
+    /// Get the image size for a preview target, if it has an image
+    pub fn preview_dimensions(&self, target: PreviewTarget) -> Option<(u32, u32)> {
+        match target {
+            PreviewTarget::Input(idx) => self
+                .input_slot_pixels
+                .get(idx)
+                .and_then(|p| p.as_ref())
+                .map(|p| p.dimensions()),
+            PreviewTarget::Output => self
+                .output_buffers
+                .get(self.current_output_index)
+                .map(|b| b.dimensions()),
+            PreviewTarget::CompareInput => {
+                self.preview_dimensions(PreviewTarget::Input(self.compare_input))
             }
         }
     }
 
-    /// Get cached handle for an input slot, or None if not available
+    /// Get the channel shown by a preview target
+    pub fn channel_view(&self, target: PreviewTarget) -> ChannelView {
+        match target {
+            PreviewTarget::Input(idx) => self.input_channels.get(idx).copied().unwrap_or_default(),
+            // Both sides of a comparison use the output's channel
+            PreviewTarget::Output | PreviewTarget::CompareInput => self.output_channel,
+        }
+    }
+
+    /// Get the alpha mode a preview target displays its image with
     ///
-    /// Returns a reference to the cached handle without regenerating it.
+    /// Inputs are shown as stored, so they follow their configured mode;
+    /// outputs are shown as rendered, which is always straight.
+    pub fn alpha_view(&self, target: PreviewTarget) -> AlphaMode {
+        let input_mode = |idx: usize| {
+            self.get_selected_shader()
+                .map(|shader| shader.input_alpha_mode(idx))
+                .unwrap_or_default()
+        };
+
+        let configured = match target {
+            PreviewTarget::Input(idx) => input_mode(idx),
+            PreviewTarget::CompareInput => input_mode(self.compare_input),
+            PreviewTarget::Output => AlphaMode::Straight,
+        };
+        self.preview_alpha.resolve(configured)
+    }
+
+    /// Set the channel shown by a preview target
+    pub fn set_channel_view(&mut self, target: PreviewTarget, channel: ChannelView) {
+        match target {
+            PreviewTarget::Input(idx) => {
+                if let Some(current) = self.input_channels.get_mut(idx) {
+                    *current = channel;
+                }
+            }
+            PreviewTarget::Output | PreviewTarget::CompareInput => self.output_channel = channel,
+        }
+    }
+
+    /// Get the rendered display handle for a preview target
+    pub fn get_display_handle(
+        &self,
+        target: PreviewTarget,
+    ) -> Option<&iced::widget::image::Handle> {
+        match target {
+            PreviewTarget::Input(idx) => self.input_displays.get(idx).and_then(|h| h.as_ref()),
+            PreviewTarget::Output => self.output_display.as_ref(),
+            PreviewTarget::CompareInput => self.compare_input_display.as_ref(),
+        }
+    }
+
+    /// Whether the output area is comparing against a loaded input
+    pub fn is_comparing(&self) -> bool {
+        self.compare_mode != CompareMode::Off
+            && self
+                .input_slot_pixels
+                .get(self.compare_input)
+                .is_some_and(|p| p.is_some())
+    }
+
+    /// Get the display handle for an input slot, or None if not available
+    ///
+    /// Picks the preview level best suited to `size` without regenerating it.
     /// Used during rendering to avoid redundant image conversions.
-    pub fn get_input_slot_handle(&self, slot_idx: usize) -> Option<&iced::widget::image::Handle> {
-        self.input_slot_handles
+    pub fn get_input_slot_handle(
+        &self,
+        slot_idx: usize,
+        size: f32,
+    ) -> Option<&iced::widget::image::Handle> {
+        self.input_slot_previews
             .get(slot_idx)
-            .and_then(|h| h.as_ref())
+            .and_then(|p| p.as_ref())
+            .map(|p| p.handle_for_size(size))
+    }
+
+    /// Capture the editable state for undo
+    pub fn snapshot(&self) -> Snapshot {
+        let slots = (0..self.input_slots.len())
+            .map(|idx| SlotSnapshot {
+                image: self.input_slots[idx].image.clone(),
+                preview: self.input_slot_previews.get(idx).cloned().flatten(),
+                pixels: self.input_slot_pixels.get(idx).cloned().flatten(),
+                layers: self.input_slot_layers.get(idx).cloned().flatten(),
+            })
+            .collect();
+
+        Snapshot {
+            selected_shader: self.selected_shader.clone(),
+            parameter_values: self.parameter_values.clone(),
+            slots,
+            current_output_index: self.current_output_index,
+        }
     }
 
-    /// Clear all input slots and cached handles
+    /// Restore the editable state from a snapshot
+    ///
+    /// Outputs are cleared and must be re-processed by the caller. The output
+    /// selection is applied once the new outputs arrive.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.parameter_values = snapshot.parameter_values;
+
+        if self.selected_shader != snapshot.selected_shader {
+            self.selected_shader = snapshot.selected_shader;
+
+            match self.get_selected_shader() {
+                Some(shader) => self.initialize_input_slots(&shader),
+                None => {
+                    self.input_slots.clear();
+                    self.input_slot_previews.clear();
+                    self.input_slot_pixels.clear();
+                    self.input_slot_generations.clear();
+                    self.input_slot_layers.clear();
+                    self.input_displays.clear();
+                    self.input_channels.clear();
+                }
+            }
+        }
+
+        for (idx, slot) in snapshot.slots.into_iter().enumerate() {
+            if idx >= self.input_slots.len() {
+                break;
+            }
+
+            // Unchanged slots keep their generation, so their uploaded
+            // textures stay cached
+            let unchanged = match (&self.input_slots[idx].image, &slot.image) {
+                (Some(current), Some(image)) => Arc::ptr_eq(current, image),
+                (None, None) => true,
+                _ => false,
+            };
+            if !unchanged {
+                self.input_slots[idx].image = slot.image;
+                self.update_input_slot_preview(idx, slot.preview, slot.pixels);
+            }
+            self.set_input_slot_layers(idx, slot.layers);
+        }
+
+        self.clear_outputs();
+        self.pending_output_index = Some(snapshot.current_output_index);
+    }
+
+    /// Clear all input slots and cached previews
     ///
     /// Removes all loaded images and resets caches while preserving
     /// the slot structure for the current shader.
@@ -194,25 +557,46 @@ impl TextureConverterState {
         for slot in &mut self.input_slots {
             slot.clear();
         }
-        self.input_slot_handles.clear();
+        self.input_slot_previews.clear();
+        self.input_slot_pixels.clear();
         self.input_slot_generations.clear();
+        self.input_slot_layers.clear();
+        self.input_displays.clear();
+        self.compare_input_display = None;
+        self.compare_display = None;
+        self.selected_frame = 0;
+        self.save_mipmaps = None;
+
+        if matches!(self.stats_target, Some(PreviewTarget::Input(_))) {
+            self.stats = None;
+        }
 
         // Recreate empty caches
         for _ in 0..self.input_slots.len() {
-            self.input_slot_handles.push(None);
+            self.input_slot_previews.push(None);
+            self.input_slot_pixels.push(None);
+            self.input_displays.push(None);
             self.input_slot_generations.push(0);
+            self.input_slot_layers.push(None);
         }
     }
 
     /// Clear all outputs
     ///
-    /// Removes all output buffers, handles, and descriptions.
+    /// Removes all output buffers, previews, and descriptions.
     /// Resets the output index to 0.
     pub fn clear_outputs(&mut self) {
+        self.outputs_are_preview = false;
         self.outputs.clear();
         self.output_buffers.clear();
         self.output_descriptions.clear();
+        self.output_display = None;
+        self.compare_display = None;
         self.current_output_index = 0;
+
+        if self.stats_target == Some(PreviewTarget::Output) {
+            self.stats = None;
+        }
     }
 
     /// Navigate to next output
@@ -235,29 +619,27 @@ impl TextureConverterState {
 
     /// Set outputs from processing results
     ///
-    /// Converts output buffers to displayable handles and stores them.
+    /// Stores output buffers with the previews built during processing.
     /// Clears any existing outputs and resets the index to 0.
-    pub fn set_outputs(&mut self, outputs: Vec<(ImageBuffer, String)>) {
+    pub fn set_outputs(&mut self, outputs: Vec<ProcessedOutput>) {
         self.outputs.clear();
         self.output_buffers.clear();
         self.output_descriptions.clear();
 
-        for (buffer, description) in outputs {
-            let handle = crate::core_logic::buffer_to_handle(&buffer);
-            self.outputs.push(handle);
-            self.output_buffers.push(buffer);
-            self.output_descriptions.push(description);
+        for output in outputs {
+            self.outputs.push(output.preview);
+            self.output_buffers.push(Arc::new(output.buffer));
+            self.output_descriptions.push(output.description);
         }
 
         self.current_output_index = 0;
     }
 
     /// Get the current output buffer for saving
-    #[allow(dead_code)]
     pub fn get_current_output(&self) -> Option<(&ImageBuffer, &str)> {
         if self.current_output_index < self.output_buffers.len() {
             Some((
-                &self.output_buffers[self.current_output_index],
+                self.output_buffers[self.current_output_index].as_ref(),
                 &self.output_descriptions[self.current_output_index],
             ))
         } else {
@@ -0,0 +1,245 @@
+//! Stats Module
+//!
+//! Per-channel histograms and statistics for input and output images.
+//!
+//! Statistics are gathered in a single pass over RGBA8 data and are meant to
+//! answer quick questions about packed textures: does a gloss map use the
+//! full range, is an alpha channel constant, is a channel left empty, and do
+//! normal maps hold unit-length vectors.
+
+use crate::components::texture_converter::viewport::PreviewTarget;
+use crate::porter_image::ImageBuffer;
+use std::fmt;
+
+/// Width of the histogram image (one column per byte value)
+const HISTOGRAM_WIDTH: u32 = 256;
+
+/// Height of the histogram image
+pub const HISTOGRAM_HEIGHT: u32 = 100;
+
+/// Allowed deviation from unit length before a normal counts as invalid
+const NORMAL_LENGTH_TOLERANCE: f32 = 0.1;
+
+/// Fraction of invalid normals that triggers a warning
+const NORMAL_WARNING_FRACTION: f32 = 0.05;
+
+/// Display names for each channel
+const CHANNEL_NAMES: [&str; 4] = ["Red", "Green", "Blue", "Alpha"];
+
+/// Histogram colours for each channel
+const CHANNEL_COLORS: [[u8; 3]; 4] = [
+    [255, 80, 80],
+    [80, 255, 80],
+    [80, 140, 255],
+    [200, 200, 200],
+];
+
+/// Statistics for a single channel, in normalised 0.0 to 1.0 values
+#[derive(Debug, Clone)]
+pub struct ChannelStats {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub histogram: [u64; 256],
+}
+
+/// Statistics for a whole RGBA8 image
+#[derive(Debug, Clone)]
+pub struct ImageStats {
+    pub width: u32,
+    pub height: u32,
+    pub channels: [ChannelStats; 4],
+    /// Pixels with alpha 0
+    pub transparent_pixels: u64,
+    /// Pixels with alpha 255
+    pub opaque_pixels: u64,
+    pub warnings: Vec<String>,
+}
+
+/// A preview that statistics can be shown for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsSource {
+    pub target: PreviewTarget,
+    pub label: String,
+}
+
+/// Statistics with their rendered histogram, ready for display
+#[derive(Debug, Clone)]
+pub struct StatsReport {
+    pub stats: ImageStats,
+    pub histogram: iced::widget::image::Handle,
+}
+
+impl StatsReport {
+    /// Gather statistics and render the histogram for a buffer
+    pub fn from_buffer(buffer: &ImageBuffer) -> Self {
+        let stats = ImageStats::from_buffer(buffer);
+        let histogram = crate::core_logic::buffer_to_handle(&stats.render_histogram());
+
+        Self { stats, histogram }
+    }
+}
+
+impl fmt::Display for StatsSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+impl ChannelStats {
+    /// Derive statistics from a byte histogram
+    fn from_histogram(histogram: [u64; 256], pixel_count: u64) -> Self {
+        let min = histogram.iter().position(|&count| count > 0).unwrap_or(0);
+        let max = histogram.iter().rposition(|&count| count > 0).unwrap_or(0);
+
+        let count = pixel_count.max(1) as f64;
+        let sum: f64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(value, &n)| value as f64 * n as f64)
+            .sum();
+        let mean = sum / count;
+        let variance: f64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(value, &n)| (value as f64 - mean).powi(2) * n as f64)
+            .sum::<f64>()
+            / count;
+
+        Self {
+            min: min as f32 / 255.0,
+            max: max as f32 / 255.0,
+            mean: (mean / 255.0) as f32,
+            std_dev: (variance.sqrt() / 255.0) as f32,
+            histogram,
+        }
+    }
+
+    /// Whether every pixel has the same value
+    pub fn is_constant(&self) -> bool {
+        self.min == self.max
+    }
+}
+
+impl ImageStats {
+    /// Gather statistics from an RGBA8 buffer
+    ///
+    /// This touches every pixel and should be called from a background task
+    /// for large images.
+    pub fn from_buffer(buffer: &ImageBuffer) -> Self {
+        let (width, height) = buffer.dimensions();
+        let mut histograms = [[0u64; 256]; 4];
+        let mut transparent_pixels = 0;
+        let mut opaque_pixels = 0;
+        let mut invalid_normals = 0u64;
+
+        for pixel in buffer.as_raw().chunks_exact(4) {
+            for (histogram, &value) in histograms.iter_mut().zip(pixel) {
+                histogram[value as usize] += 1;
+            }
+
+            match pixel[3] {
+                0 => transparent_pixels += 1,
+                255 => opaque_pixels += 1,
+                _ => {}
+            }
+
+            if !is_unit_normal([pixel[0], pixel[1], pixel[2]]) {
+                invalid_normals += 1;
+            }
+        }
+
+        let pixel_count = width as u64 * height as u64;
+        let channels =
+            histograms.map(|histogram| ChannelStats::from_histogram(histogram, pixel_count));
+
+        let mut stats = Self {
+            width,
+            height,
+            channels,
+            transparent_pixels,
+            opaque_pixels,
+            warnings: Vec::new(),
+        };
+        stats.warnings = stats.collect_warnings(invalid_normals, pixel_count);
+        stats
+    }
+
+    /// Build warnings for common packing mistakes
+    ///
+    /// The normal length check only runs for images that look like tangent
+    /// space normal maps (blue mostly above 0.5), so colour maps don't warn.
+    fn collect_warnings(&self, invalid_normals: u64, pixel_count: u64) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (name, channel) in CHANNEL_NAMES.iter().zip(&self.channels).take(3) {
+            if channel.max == 0.0 {
+                warnings.push(format!("{name} channel unused"));
+            } else if channel.is_constant() {
+                warnings.push(format!("{name} constant {:.3}", channel.min));
+            }
+        }
+
+        let alpha = &self.channels[3];
+        if alpha.is_constant() {
+            warnings.push(format!("Alpha constant {:.3}", alpha.min));
+        }
+
+        let looks_like_normal_map = self.channels[2].mean > 0.5;
+        let invalid_fraction = invalid_normals as f32 / pixel_count.max(1) as f32;
+        if looks_like_normal_map && invalid_fraction > NORMAL_WARNING_FRACTION {
+            warnings.push(format!(
+                "Normal vectors not unit length ({:.1}% of pixels)",
+                invalid_fraction * 100.0
+            ));
+        }
+
+        warnings
+    }
+
+    /// Render the per-channel histograms into one image
+    ///
+    /// Each channel is scaled to its own tallest bin and drawn additively, so
+    /// overlapping channels stay visible.
+    pub fn render_histogram(&self) -> ImageBuffer {
+        let mut image = ImageBuffer::from_pixel(HISTOGRAM_WIDTH, HISTOGRAM_HEIGHT, [0, 0, 0, 0]);
+
+        for (channel, color) in self.channels.iter().zip(CHANNEL_COLORS) {
+            let peak = channel.histogram.iter().copied().max().unwrap_or(0).max(1) as f32;
+
+            for (x, &count) in channel.histogram.iter().enumerate() {
+                let bar = (count as f32 / peak * HISTOGRAM_HEIGHT as f32).round() as u32;
+
+                for y in HISTOGRAM_HEIGHT - bar.min(HISTOGRAM_HEIGHT)..HISTOGRAM_HEIGHT {
+                    let existing = image.get_pixel(x as u32, y).unwrap_or([0; 4]);
+                    let blended = [
+                        existing[0].saturating_add(color[0] / 2),
+                        existing[1].saturating_add(color[1] / 2),
+                        existing[2].saturating_add(color[2] / 2),
+                        255,
+                    ];
+                    image.put_pixel(x as u32, y, blended);
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Summary line for one channel
+    pub fn channel_summary(&self, index: usize) -> String {
+        let channel = &self.channels[index];
+        format!(
+            "{:<5} min {:.3}  max {:.3}  mean {:.3}  std {:.3}",
+            CHANNEL_NAMES[index], channel.min, channel.max, channel.mean, channel.std_dev
+        )
+    }
+}
+
+/// Whether RGB bytes decode to a unit-length normal vector
+fn is_unit_normal(rgb: [u8; 3]) -> bool {
+    let [x, y, z] = rgb.map(|c| c as f32 / 255.0 * 2.0 - 1.0);
+    let length = (x * x + y * y + z * z).sqrt();
+    (length - 1.0).abs() <= NORMAL_LENGTH_TOLERANCE
+}
@@ -0,0 +1,184 @@
+//! Tiling Module
+//!
+//! Splits images that exceed the GPU texture limit into overlapping tiles.
+//!
+//! Each tile covers an interior region of the final output plus an apron of
+//! extra texels on every side that isn't clamped by the image edge. Shaders
+//! that sample neighbouring texels read valid data from the apron, and only
+//! the interior is stitched back into the full-size output.
+
+/// Result type for tiling operations
+pub type TileResult<T> = Result<T, String>;
+
+/// A single tile of a larger image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    /// Rendered region including the apron (in output texels)
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Interior region written to the final output (in output texels)
+    pub inner_x: u32,
+    pub inner_y: u32,
+    pub inner_width: u32,
+    pub inner_height: u32,
+}
+
+impl Tile {
+    /// Offset of the interior region relative to the rendered region
+    pub fn inner_offset(&self) -> (u32, u32) {
+        (self.inner_x - self.x, self.inner_y - self.y)
+    }
+
+    /// Map the rendered region into the texel space of an input image
+    ///
+    /// Inputs don't have to match the output size, so the region is scaled
+    /// proportionally and expanded to whole texels.
+    pub fn source_region(
+        &self,
+        output_size: (u32, u32),
+        source_size: (u32, u32),
+    ) -> (u32, u32, u32, u32) {
+        let (out_w, out_h) = (output_size.0 as u64, output_size.1 as u64);
+        let (src_w, src_h) = (source_size.0 as u64, source_size.1 as u64);
+
+        let x0 = (self.x as u64 * src_w) / out_w;
+        let y0 = (self.y as u64 * src_h) / out_h;
+        let x1 = ((self.x + self.width) as u64 * src_w).div_ceil(out_w);
+        let y1 = ((self.y + self.height) as u64 * src_h).div_ceil(out_h);
+
+        let x1 = x1.clamp(x0 + 1, src_w);
+        let y1 = y1.clamp(y0 + 1, src_h);
+
+        (x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32)
+    }
+}
+
+/// Size an input is resampled to before tiles are cropped from it
+///
+/// Tiles are planned in output texels, so a crop of an input larger than the
+/// output is scaled up and can exceed the texture limit. Inputs beyond the
+/// limit are resampled to the output size, making every crop the size of its
+/// tile; crops of smaller inputs never exceed the input itself.
+pub fn tile_source_size(
+    output_size: (u32, u32),
+    source_size: (u32, u32),
+    max_dimension: u32,
+) -> (u32, u32) {
+    if source_size.0 > max_dimension || source_size.1 > max_dimension {
+        output_size
+    } else {
+        source_size
+    }
+}
+
+/// Plan the tiles required to cover an image
+///
+/// # Arguments
+/// * `width`, `height` - Full output dimensions
+/// * `max_dimension` - Largest texture dimension the GPU supports
+/// * `apron` - Extra texels rendered around each tile's interior
+///
+/// # Returns
+/// Tiles in row-major order. An image that fits within `max_dimension`
+/// produces a single tile covering the whole image.
+pub fn plan_tiles(
+    width: u32,
+    height: u32,
+    max_dimension: u32,
+    apron: u32,
+) -> TileResult<Vec<Tile>> {
+    if width == 0 || height == 0 {
+        return Err(format!("Invalid image dimensions: {width}x{height}"));
+    }
+
+    if apron.saturating_mul(2) >= max_dimension {
+        return Err(format!(
+            "Tile apron of {apron}px is too large for the GPU texture limit of {max_dimension}px"
+        ));
+    }
+
+    // Interior size of each tile, leaving room for the apron on both sides
+    let step = max_dimension - apron * 2;
+
+    let mut tiles = Vec::new();
+    let mut inner_y = 0;
+
+    while inner_y < height {
+        let inner_height = step.min(height - inner_y);
+        let mut inner_x = 0;
+
+        while inner_x < width {
+            let inner_width = step.min(width - inner_x);
+
+            let x = inner_x.saturating_sub(apron);
+            let y = inner_y.saturating_sub(apron);
+            let right = (inner_x + inner_width + apron).min(width);
+            let bottom = (inner_y + inner_height + apron).min(height);
+
+            tiles.push(Tile {
+                x,
+                y,
+                width: right - x,
+                height: bottom - y,
+                inner_x,
+                inner_y,
+                inner_width,
+                inner_height,
+            });
+
+            inner_x += inner_width;
+        }
+
+        inner_y += inner_height;
+    }
+
+    Ok(tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_region_scales_to_larger_source() {
+        let tile = Tile {
+            x: 100,
+            y: 50,
+            width: 200,
+            height: 100,
+            inner_x: 100,
+            inner_y: 50,
+            inner_width: 200,
+            inner_height: 100,
+        };
+
+        assert_eq!(
+            tile.source_region((1000, 500), (2000, 1000)),
+            (200, 100, 400, 200)
+        );
+    }
+
+    #[test]
+    fn oversized_source_crops_fit_the_limit() {
+        let max_dimension = 8192;
+        let output_size = (8192, 8192);
+        let source_size = tile_source_size(output_size, (16384, 16384), max_dimension);
+        assert_eq!(source_size, output_size);
+
+        for tile in plan_tiles(output_size.0, output_size.1, max_dimension, 16).unwrap() {
+            let (x, y, width, height) = tile.source_region(output_size, source_size);
+            assert!(width <= max_dimension && height <= max_dimension);
+            assert!(x + width <= source_size.0 && y + height <= source_size.1);
+        }
+    }
+
+    #[test]
+    fn sources_within_the_limit_keep_their_size() {
+        assert_eq!(
+            tile_source_size((20000, 20000), (4096, 2048), 8192),
+            (4096, 2048)
+        );
+    }
+}
@@ -1,6 +1,13 @@
 //! Type definitions and error types for the texture converter module
 
+use crate::components::texture_converter::dither::Dithering;
+use crate::components::texture_converter::sizing::OutputSizes;
+use crate::float_images::{ExrLayers, FloatRange, FloatRemap};
+use crate::porter_image::{ImageBuffer, PorterImage, SaveOptions};
+use crate::preview::PreviewPyramid;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Result type for texture converter operations
 #[allow(dead_code)]
@@ -18,7 +25,72 @@ pub type GpuResult<T> = Result<T, GpuError>;
 #[allow(dead_code)]
 pub type FileResult<T> = Result<T, FileError>;
 
-/// Supported output image formats (PorterLib supported formats only)
+/// An input image loaded from disk along with its display preview
+#[derive(Debug, Clone)]
+pub struct LoadedImage {
+    pub image: PorterImage,
+    /// File the image was loaded from
+    pub path: PathBuf,
+    /// Full-resolution RGBA8 copy used for zoomed views and inspection
+    pub pixels: Arc<ImageBuffer>,
+    pub preview: PreviewPyramid,
+    /// Layers offered by an EXR file
+    pub layers: Option<ExrLayers>,
+    /// How a float file's values were brought into 0-1, if they had to be
+    pub remap: Option<FloatRemap>,
+}
+
+impl LoadedImage {
+    /// Load an image and build its preview pyramid
+    ///
+    /// Runs the full decode and downscale, so call from a background task.
+    pub fn open(path: &std::path::Path, range: FloatRange) -> Result<Self, String> {
+        Self::open_layer(path, None, range)
+    }
+
+    /// Load one layer of a multi-layer EXR, or the image for other formats
+    pub fn open_layer(
+        path: &std::path::Path,
+        layer: Option<&str>,
+        range: FloatRange,
+    ) -> Result<Self, String> {
+        let (image, info) = PorterImage::open_layer(path, layer, range)?;
+        let pixels = image.to_rgba8_image_buffer()?;
+        let preview = PreviewPyramid::from_buffer(&pixels);
+
+        Ok(Self {
+            image,
+            path: path.to_path_buf(),
+            pixels: Arc::new(pixels),
+            preview,
+            layers: info.layers,
+            remap: info.remap,
+        })
+    }
+}
+
+/// A processed shader output along with its display preview
+#[derive(Debug, Clone)]
+pub struct ProcessedOutput {
+    pub buffer: ImageBuffer,
+    pub description: String,
+    pub preview: PreviewPyramid,
+}
+
+impl ProcessedOutput {
+    /// Wrap a processed buffer and build its preview pyramid
+    pub fn new(buffer: ImageBuffer, description: String) -> Self {
+        let preview = PreviewPyramid::from_buffer(&buffer);
+
+        Self {
+            buffer,
+            description,
+            preview,
+        }
+    }
+}
+
+/// Supported output image formats (PorterLib formats plus EXR and KTX2)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ImageFormat {
     #[default]
@@ -26,6 +98,8 @@ pub enum ImageFormat {
     Tga,
     Tiff,
     Dds,
+    Exr,
+    Ktx2,
 }
 
 impl ImageFormat {
@@ -36,6 +110,8 @@ impl ImageFormat {
             ImageFormat::Tga => "tga",
             ImageFormat::Tiff => "tiff",
             ImageFormat::Dds => "dds",
+            ImageFormat::Exr => "exr",
+            ImageFormat::Ktx2 => "ktx2",
         }
     }
 
@@ -46,15 +122,19 @@ impl ImageFormat {
             ImageFormat::Tga => "TGA",
             ImageFormat::Tiff => "TIFF",
             ImageFormat::Dds => "DDS",
+            ImageFormat::Exr => "EXR",
+            ImageFormat::Ktx2 => "KTX2",
         }
     }
 
     /// Get all available formats
-    pub const ALL: [ImageFormat; 4] = [
+    pub const ALL: [ImageFormat; 6] = [
         ImageFormat::Png,
         ImageFormat::Tga,
         ImageFormat::Tiff,
         ImageFormat::Dds,
+        ImageFormat::Exr,
+        ImageFormat::Ktx2,
     ];
 }
 
@@ -64,6 +144,35 @@ impl std::fmt::Display for ImageFormat {
     }
 }
 
+/// File formats and encoder settings used when saving outputs
+#[derive(Debug, Clone, Default)]
+pub struct SaveFormats {
+    /// Format for outputs without an override
+    pub default: ImageFormat,
+    /// Per-output formats keyed by output description
+    pub overrides: HashMap<String, ImageFormat>,
+    pub options: SaveOptions,
+    /// Settings of outputs rendered for the save
+    pub render: RenderOptions,
+}
+
+impl SaveFormats {
+    /// Format an output is saved in
+    pub fn for_output(&self, description: &str) -> ImageFormat {
+        self.overrides
+            .get(description)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// Per-output settings applied while rendering
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    pub dithering: Dithering,
+    pub sizes: OutputSizes,
+}
+
 /// Main error type for texture converter operations
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
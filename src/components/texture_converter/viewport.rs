@@ -0,0 +1,187 @@
+//! Viewport Module
+//!
+//! Shared zoom and pan for the input and output previews.
+//!
+//! The view transform is stored relative to each image's "fit" scale and
+//! centred on a UV coordinate, so images of different sizes stay aligned
+//! when the same transform is applied to all of them. Zoomed views are
+//! rendered on the CPU with nearest sampling straight from the
+//! full-resolution pixels, so only the visible texels are ever touched.
+
+use crate::components::texture_converter::alpha::AlphaMode;
+use crate::components::texture_converter::channels::ChannelView;
+use crate::porter_image::ImageBuffer;
+use iced::Point;
+use iced::mouse::ScrollDelta;
+
+/// Maximum zoom relative to the fitted view
+pub const MAX_ZOOM: f32 = 512.0;
+
+/// Zoom multiplier for one scroll wheel line
+const ZOOM_STEP: f32 = 1.25;
+
+/// Pixels of smooth scrolling that count as one wheel line
+const PIXELS_PER_LINE: f32 = 50.0;
+
+/// A preview that can be zoomed, panned and inspected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreviewTarget {
+    Input(usize),
+    Output,
+    /// The input shown next to the output in side-by-side compare
+    CompareInput,
+}
+
+/// Zoom and pan shared by every preview
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewTransform {
+    /// Zoom relative to the fitted view (1.0 = whole image visible)
+    pub zoom: f32,
+    /// UV coordinate shown at the centre of the view
+    pub center: (f32, f32),
+}
+
+impl Default for ViewTransform {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            center: (0.5, 0.5),
+        }
+    }
+}
+
+impl ViewTransform {
+    /// Whether the whole image is visible without zoom
+    pub fn is_fitted(&self) -> bool {
+        self.zoom <= 1.0
+    }
+
+    /// Display pixels per texel for an image shown in a square view
+    pub fn scale(&self, image_size: (u32, u32), view_size: f32) -> f32 {
+        let fit =
+            (view_size / image_size.0.max(1) as f32).min(view_size / image_size.1.max(1) as f32);
+        fit * self.zoom
+    }
+
+    /// Convert a point in the view to a UV coordinate on the image
+    pub fn display_to_uv(
+        &self,
+        point: Point,
+        image_size: (u32, u32),
+        view_size: f32,
+    ) -> (f32, f32) {
+        let scale = self.scale(image_size, view_size);
+        let half = view_size / 2.0;
+
+        (
+            self.center.0 + (point.x - half) / (scale * image_size.0.max(1) as f32),
+            self.center.1 + (point.y - half) / (scale * image_size.1.max(1) as f32),
+        )
+    }
+
+    /// Zoom by a factor while keeping the texel under `point` in place
+    pub fn zoom_at(&mut self, factor: f32, point: Point, image_size: (u32, u32), view_size: f32) {
+        let anchor = self.display_to_uv(point, image_size, view_size);
+        let zoom = (self.zoom * factor).clamp(1.0, MAX_ZOOM);
+
+        if zoom <= 1.0 {
+            *self = Self::default();
+            return;
+        }
+
+        self.zoom = zoom;
+
+        let scale = self.scale(image_size, view_size);
+        let half = view_size / 2.0;
+        self.center = (
+            anchor.0 - (point.x - half) / (scale * image_size.0.max(1) as f32),
+            anchor.1 - (point.y - half) / (scale * image_size.1.max(1) as f32),
+        );
+        self.clamp_center();
+    }
+
+    /// Pan by a drag distance in display pixels
+    pub fn pan_by(&mut self, dx: f32, dy: f32, image_size: (u32, u32), view_size: f32) {
+        if self.is_fitted() {
+            return;
+        }
+
+        let scale = self.scale(image_size, view_size);
+        self.center.0 -= dx / (scale * image_size.0.max(1) as f32);
+        self.center.1 -= dy / (scale * image_size.1.max(1) as f32);
+        self.clamp_center();
+    }
+
+    /// Keep the centre of the view on the image
+    fn clamp_center(&mut self) {
+        self.center.0 = self.center.0.clamp(0.0, 1.0);
+        self.center.1 = self.center.1.clamp(0.0, 1.0);
+    }
+}
+
+/// Convert a scroll wheel movement into a zoom factor
+pub fn scroll_zoom_factor(delta: ScrollDelta) -> f32 {
+    match delta {
+        ScrollDelta::Lines { y, .. } => ZOOM_STEP.powf(y),
+        ScrollDelta::Pixels { y, .. } => ZOOM_STEP.powf(y / PIXELS_PER_LINE),
+    }
+}
+
+/// Convert a UV coordinate to a texel, or None if it falls outside the image
+pub fn uv_to_texel(uv: (f32, f32), image_size: (u32, u32)) -> Option<(u32, u32)> {
+    if !(0.0..1.0).contains(&uv.0) || !(0.0..1.0).contains(&uv.1) {
+        return None;
+    }
+
+    let x = ((uv.0 * image_size.0 as f32) as u32).min(image_size.0.saturating_sub(1));
+    let y = ((uv.1 * image_size.1 as f32) as u32).min(image_size.1.saturating_sub(1));
+
+    Some((x, y))
+}
+
+/// Render the visible part of an image into a square view
+///
+/// Each display pixel takes the nearest texel, so zoomed views show
+/// individual texels as solid blocks. Areas outside the image are transparent.
+pub fn render_viewport(
+    source: &ImageBuffer,
+    view: &ViewTransform,
+    view_size: f32,
+    channel: ChannelView,
+    alpha: AlphaMode,
+) -> ImageBuffer {
+    let size = view_size.round().max(1.0) as u32;
+    let image_size = source.dimensions();
+    let mut data = vec![0u8; size as usize * size as usize * 4];
+
+    for py in 0..size {
+        for px in 0..size {
+            let point = Point::new(px as f32 + 0.5, py as f32 + 0.5);
+            let uv = view.display_to_uv(point, image_size, view_size);
+
+            if let Some(pixel) =
+                uv_to_texel(uv, image_size).and_then(|(x, y)| source.get_pixel(x, y))
+            {
+                let offset = (py as usize * size as usize + px as usize) * 4;
+                data[offset..offset + 4].copy_from_slice(&channel.map_pixel(pixel, alpha, px, py));
+            }
+        }
+    }
+
+    ImageBuffer::from_raw(size, size, data).expect("viewport has matching dimensions")
+}
+
+/// Format a texel readout as bytes and normalised floats
+pub fn format_texel(label: &str, texel: (u32, u32), pixel: [u8; 4]) -> String {
+    let [r, g, b, a] = pixel;
+
+    format!(
+        "{label} ({}, {}): {r:>3} {g:>3} {b:>3} {a:>3} | {:.3} {:.3} {:.3} {:.3}",
+        texel.0,
+        texel.1,
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        a as f32 / 255.0,
+    )
+}
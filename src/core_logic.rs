@@ -1,52 +1,4 @@
-use crate::porter_image::{ImageBuffer, PorterImage};
-
-/// Convert a PorterImage to iced display handle
-pub fn image_to_handle(img: &mut PorterImage) -> iced::widget::image::Handle {
-    match img.convert_to_rgba8() {
-        Ok(_) => {
-            let width = img.width();
-            let height = img.height();
-
-            if width < 1 || height < 1 {
-                tracing::error!("Image dimensions too small: {width}x{height}");
-                return create_fallback_handle();
-            }
-            if width > 8192 || height > 8192 {
-                tracing::error!("Image dimensions too large: {width}x{height}");
-                return create_fallback_handle();
-            }
-
-            match img.as_rgba8_buffer() {
-                Ok(buffer) => {
-                    let bytes = buffer.to_vec();
-
-                    // Validate byte length
-                    let expected_len = (width as usize)
-                        .checked_mul(height as usize)
-                        .and_then(|v| v.checked_mul(4));
-
-                    match expected_len {
-                        Some(len) if bytes.len() == len => {
-                            iced::widget::image::Handle::from_rgba(width, height, bytes)
-                        }
-                        _ => {
-                            tracing::error!("Buffer length mismatch");
-                            create_fallback_handle()
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to get RGBA8 buffer: {}", e);
-                    create_fallback_handle()
-                }
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to convert to RGBA8: {}", e);
-            create_fallback_handle()
-        }
-    }
-}
+use crate::porter_image::ImageBuffer;
 
 /// Convert an image buffer to an iced::image::Handle for display.
 /// Validates dimensions to prevent rendering panics.
@@ -0,0 +1,492 @@
+//! Floating-point image formats.
+//!
+//! OpenEXR (half, float and integer channels) and Radiance `.hdr` files are
+//! read here into RGBA8 buffers, and outputs can be written back out as
+//! half-float EXR. Float values are treated as data rather than colour: they
+//! map linearly onto 0-255, so masks and height maps keep their values
+//! without any tone mapping or gamma.
+//!
+//! This is not a float pipeline. Inputs are quantised to 8 bits per channel
+//! before they reach the shaders, and EXR outputs are written from the 8-bit
+//! result, so precision beyond 1/255 is lost both ways. Colour values outside
+//! [0, 1], as in HDR lighting or height data, are brought into range as the
+//! `FloatRange` setting picks: normalized from the file's own range, or
+//! clamped. Either way the loader reports the original range so the UI can
+//! warn about it. Alpha is always clamped, and NaN reads as 0.
+//!
+//! Multi-layer EXR files offer each layer separately. Channels are grouped by
+//! their part name and their `layer.` prefix; R, G, B and A channels (or a
+//! single Y channel) form a colour layer, and any other channel such as `Z`
+//! is offered as a greyscale layer of its own.
+
+use crate::porter_image::ImageBuffer;
+use exr::prelude::{AnyChannels, FlatImage, FlatSamples, Layer, Text};
+use std::io::Cursor;
+use std::path::Path;
+
+/// Name given to channels without a layer name or prefix
+const DEFAULT_LAYER: &str = "default";
+
+/// Largest width or height accepted from an HDR header
+const MAX_HDR_DIMENSION: usize = 65536;
+
+/// Most pixels one byte of HDR data can hold: a two-byte run covers at most
+/// 127 values of one of the four channels
+const MAX_HDR_PIXELS_PER_BYTE: usize = 16;
+
+/// How float colour values outside [0, 1] are brought into 8-bit range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatRange {
+    /// Map the file's lowest and highest colour values onto 0 and 1
+    #[default]
+    Normalize,
+    /// Cut values off at 0 and 1
+    Clamp,
+}
+
+impl FloatRange {
+    /// Get display name for this setting
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FloatRange::Normalize => "Float: Normalize",
+            FloatRange::Clamp => "Float: Clamp",
+        }
+    }
+
+    /// Get all available settings
+    pub const ALL: [FloatRange; 2] = [FloatRange::Normalize, FloatRange::Clamp];
+}
+
+impl std::fmt::Display for FloatRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Colour range of a float file that didn't fit [0, 1], and how it was read
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatRemap {
+    pub range: FloatRange,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl FloatRemap {
+    /// Remap for a set of colour samples, None when they already fit [0, 1]
+    fn for_samples<'a>(samples: impl Iterator<Item = &'a f32>, range: FloatRange) -> Option<Self> {
+        let (min, max) = samples
+            .filter(|value| value.is_finite())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+
+        (min < 0.0 || max > 1.0).then_some(Self { range, min, max })
+    }
+
+    /// Bring a colour sample into the range `to_unorm8` stores
+    fn apply(&self, value: f32) -> f32 {
+        match self.range {
+            FloatRange::Normalize if self.max > self.min => {
+                (value - self.min) / (self.max - self.min)
+            }
+            _ => value,
+        }
+    }
+
+    /// Warning describing how the file's values were changed
+    pub fn warning(&self) -> String {
+        let action = match self.range {
+            FloatRange::Normalize => "normalized",
+            FloatRange::Clamp => "clamped",
+        };
+        format!(
+            "float values from {:.3} to {:.3} were {action} to 0-1",
+            self.min, self.max
+        )
+    }
+}
+
+/// What reading a float file found besides its pixels
+#[derive(Debug, Clone, Default)]
+pub struct FloatInfo {
+    /// Layers offered by an EXR file
+    pub layers: Option<ExrLayers>,
+    /// Set when colour values had to be brought into [0, 1]
+    pub remap: Option<FloatRemap>,
+}
+
+/// Layers an EXR file offers and the one that was read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExrLayers {
+    pub names: Vec<String>,
+    pub selected: String,
+}
+
+/// Indices of the channels that make up one layer
+#[derive(Debug)]
+struct ChannelGroup {
+    name: String,
+    /// Index of the EXR part the channels belong to
+    part: usize,
+    /// Red, green, blue, alpha and grey channel indices
+    channels: [Option<usize>; 5],
+}
+
+impl ChannelGroup {
+    /// Whether the group has any colour channels
+    fn is_colour(&self) -> bool {
+        self.channels[..3].iter().any(Option::is_some)
+    }
+}
+
+/// Decode one layer of an EXR file
+///
+/// Reads the named layer, or the first colour layer when `layer` is None.
+/// Returns the layers the file offers and any remapping alongside the pixels.
+pub fn decode_exr(
+    data: &[u8],
+    layer: Option<&str>,
+    range: FloatRange,
+) -> Result<(ImageBuffer, FloatInfo), String> {
+    let image: FlatImage = exr::prelude::read()
+        .no_deep_data()
+        .largest_resolution_level()
+        .all_channels()
+        .all_layers()
+        .all_attributes()
+        .from_buffered(Cursor::new(data))
+        .map_err(|e| format!("Invalid EXR: {e}"))?;
+
+    let groups = channel_groups(&image);
+    let group = match layer {
+        Some(name) => groups
+            .iter()
+            .find(|group| group.name == name)
+            .ok_or_else(|| format!("EXR has no layer named {name}"))?,
+        None => groups
+            .iter()
+            .find(|group| group.is_colour())
+            .or_else(|| groups.first())
+            .ok_or_else(|| "EXR has no channels".to_string())?,
+    };
+
+    let (buffer, remap) = group_to_rgba8(&image.layer_data[group.part], group, range)?;
+    let layers = ExrLayers {
+        names: groups.iter().map(|group| group.name.clone()).collect(),
+        selected: group.name.clone(),
+    };
+
+    Ok((
+        buffer,
+        FloatInfo {
+            layers: Some(layers),
+            remap,
+        },
+    ))
+}
+
+/// Group the channels of every part into layers, in file order
+fn channel_groups(image: &FlatImage) -> Vec<ChannelGroup> {
+    let mut groups: Vec<ChannelGroup> = Vec::new();
+
+    for (part, layer) in image.layer_data.iter().enumerate() {
+        let part_name = layer.attributes.layer_name.as_ref().map(Text::to_string);
+
+        for (idx, channel) in layer.channel_data.list.iter().enumerate() {
+            let channel_name = channel.name.to_string();
+            let (prefix, suffix) = channel_name
+                .rsplit_once('.')
+                .unwrap_or(("", channel_name.as_str()));
+
+            let role = match suffix.to_ascii_uppercase().as_str() {
+                "R" | "RED" => Some(0),
+                "G" | "GREEN" => Some(1),
+                "B" | "BLUE" => Some(2),
+                "A" | "ALPHA" => Some(3),
+                "Y" => Some(4),
+                _ => None,
+            };
+
+            // Channels that aren't colour components become their own layer
+            let (name, role) = match role {
+                Some(role) => (layer_name(part_name.as_deref(), prefix), role),
+                None => (layer_name(part_name.as_deref(), &channel_name), 4),
+            };
+
+            let position = groups
+                .iter()
+                .position(|group| group.part == part && group.name == name);
+            let group_idx = match position {
+                Some(group_idx) => group_idx,
+                None => {
+                    groups.push(ChannelGroup {
+                        name,
+                        part,
+                        channels: [None; 5],
+                    });
+                    groups.len() - 1
+                }
+            };
+
+            groups[group_idx].channels[role].get_or_insert(idx);
+        }
+    }
+
+    groups
+}
+
+/// Full name of a layer from its part name and channel prefix
+fn layer_name(part_name: Option<&str>, prefix: &str) -> String {
+    match (part_name, prefix) {
+        (None | Some(""), "") => DEFAULT_LAYER.to_string(),
+        (None | Some(""), prefix) => prefix.to_string(),
+        (Some(part_name), "") => part_name.to_string(),
+        (Some(part_name), prefix) => format!("{part_name}.{prefix}"),
+    }
+}
+
+/// Convert the channels of a group to RGBA8
+///
+/// Missing colour channels fall back to the grey channel, then to 0; a
+/// missing alpha channel is opaque. Colour values outside [0, 1] are
+/// remapped as `range` picks.
+fn group_to_rgba8(
+    layer: &Layer<AnyChannels<FlatSamples>>,
+    group: &ChannelGroup,
+    range: FloatRange,
+) -> Result<(ImageBuffer, Option<FloatRemap>), String> {
+    let width = layer.size.width();
+    let height = layer.size.height();
+    let pixel_count = width * height;
+
+    let samples = |channel: Option<usize>| -> Option<Vec<f32>> {
+        channel.map(|idx| {
+            layer.channel_data.list[idx]
+                .sample_data
+                .values_as_f32()
+                .collect()
+        })
+    };
+
+    let [red, green, blue, alpha, grey] = group.channels.map(samples);
+    let red = red.or_else(|| grey.clone());
+    let green = green.or_else(|| grey.clone());
+    let blue = blue.or(grey);
+
+    let value = |channel: &Option<Vec<f32>>, idx: usize, default: f32| {
+        channel
+            .as_ref()
+            .and_then(|values| values.get(idx).copied())
+            .unwrap_or(default)
+    };
+
+    let remap =
+        FloatRemap::for_samples([&red, &green, &blue].into_iter().flatten().flatten(), range);
+    let colour = |channel: &Option<Vec<f32>>, idx: usize| {
+        let value = value(channel, idx, 0.0);
+        to_unorm8(remap.map_or(value, |remap| remap.apply(value)))
+    };
+
+    let mut rgba = Vec::with_capacity(pixel_count * 4);
+    for idx in 0..pixel_count {
+        rgba.extend_from_slice(&[
+            colour(&red, idx),
+            colour(&green, idx),
+            colour(&blue, idx),
+            to_unorm8(value(&alpha, idx, 1.0)),
+        ]);
+    }
+
+    let buffer = ImageBuffer::from_raw(width as u32, height as u32, rgba)
+        .ok_or_else(|| "Invalid EXR: pixel data size mismatch".to_string())?;
+    Ok((buffer, remap))
+}
+
+/// Write an RGBA8 buffer as a half-float EXR
+///
+/// The samples are 8-bit values stored as halves, not rendered float data.
+pub fn save_exr(buffer: &ImageBuffer, path: &Path) -> Result<(), String> {
+    use exr::prelude::f16;
+
+    let width = buffer.width() as usize;
+    let data = buffer.as_raw();
+    let sample = |x: usize, y: usize, channel: usize| {
+        f16::from_f32(data[(y * width + x) * 4 + channel] as f32 / 255.0)
+    };
+
+    exr::prelude::write_rgba_file(path, width, buffer.height() as usize, |x, y| {
+        (
+            sample(x, y, 0),
+            sample(x, y, 1),
+            sample(x, y, 2),
+            sample(x, y, 3),
+        )
+    })
+    .map_err(|e| format!("Failed to write EXR: {e}"))
+}
+
+/// Decode a Radiance RGBE image
+///
+/// Reads flat and run-length encoded scanlines in the standard `-Y +X`
+/// orientation or flipped vertically. Old-style run-length encoding and XYZE
+/// files are rejected. Values above 1 are remapped as `range` picks.
+pub fn decode_hdr(data: &[u8], range: FloatRange) -> Result<(ImageBuffer, FloatInfo), String> {
+    let mut offset = 0;
+
+    let signature =
+        next_line(data, &mut offset).ok_or_else(|| "Invalid HDR: missing header".to_string())?;
+    if !signature.starts_with(b"#?") {
+        return Err("Invalid HDR: missing #? signature".to_string());
+    }
+
+    loop {
+        let line = next_line(data, &mut offset)
+            .ok_or_else(|| "Invalid HDR: truncated header".to_string())?;
+        if line.is_empty() {
+            break;
+        }
+
+        let format = line.strip_prefix(b"FORMAT=");
+        if format.is_some_and(|format| format != b"32-bit_rle_rgbe") {
+            return Err("Unsupported HDR: only RGBE pixels are supported".to_string());
+        }
+    }
+
+    let resolution = next_line(data, &mut offset)
+        .and_then(|line| std::str::from_utf8(line).ok())
+        .ok_or_else(|| "Invalid HDR: missing resolution".to_string())?;
+    let (height, width, bottom_up) = match resolution.split_whitespace().collect::<Vec<_>>()[..] {
+        ["-Y", height, "+X", width] => (height, width, false),
+        ["+Y", height, "+X", width] => (height, width, true),
+        _ => return Err(format!("Unsupported HDR orientation: {resolution}")),
+    };
+    let parse = |value: &str| {
+        value
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid HDR resolution: {resolution}"))
+    };
+    let (width, height) = (parse(width)?, parse(height)?);
+
+    // The header is untrusted, so check the size before allocating for it
+    if width == 0 || height == 0 || width > MAX_HDR_DIMENSION || height > MAX_HDR_DIMENSION {
+        return Err(format!("Invalid HDR resolution: {resolution}"));
+    }
+    let pixel_count = width
+        .checked_mul(height)
+        .filter(|&count| count / MAX_HDR_PIXELS_PER_BYTE <= data.len() - offset)
+        .ok_or_else(|| "Invalid HDR: resolution is larger than the pixel data".to_string())?;
+    let rgbe_size = pixel_count
+        .checked_mul(4)
+        .ok_or_else(|| "Invalid HDR: image is too large".to_string())?;
+
+    let mut rgbe = vec![0u8; rgbe_size];
+    for row in 0..height {
+        let target = if bottom_up { height - 1 - row } else { row };
+        let scanline = &mut rgbe[target * width * 4..(target + 1) * width * 4];
+        offset = read_scanline(data, offset, scanline, width)?;
+    }
+
+    let values: Vec<f32> = rgbe
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            let scale = match pixel[3] {
+                0 => 0.0,
+                exponent => 2f32.powi(exponent as i32 - 136),
+            };
+            [
+                pixel[0] as f32 * scale,
+                pixel[1] as f32 * scale,
+                pixel[2] as f32 * scale,
+            ]
+        })
+        .collect();
+    let remap = FloatRemap::for_samples(values.iter(), range);
+    let colour = |value: f32| to_unorm8(remap.map_or(value, |remap| remap.apply(value)));
+
+    let rgba = values
+        .chunks_exact(3)
+        .flat_map(|rgb| [colour(rgb[0]), colour(rgb[1]), colour(rgb[2]), 255])
+        .collect();
+
+    let buffer = ImageBuffer::from_raw(width as u32, height as u32, rgba)
+        .ok_or_else(|| "Invalid HDR: pixel data size mismatch".to_string())?;
+    Ok((
+        buffer,
+        FloatInfo {
+            layers: None,
+            remap,
+        },
+    ))
+}
+
+/// Read a line of the header, without its newline
+fn next_line<'a>(data: &'a [u8], offset: &mut usize) -> Option<&'a [u8]> {
+    let rest = data.get(*offset..)?;
+    let end = rest.iter().position(|&byte| byte == b'\n')?;
+    *offset += end + 1;
+    Some(&rest[..end])
+}
+
+/// Read one scanline of RGBE pixels, returning the offset after it
+fn read_scanline(
+    data: &[u8],
+    mut offset: usize,
+    scanline: &mut [u8],
+    width: usize,
+) -> Result<usize, String> {
+    let truncated = || "Invalid HDR: truncated pixel data".to_string();
+
+    let header = data.get(offset..offset + 4).ok_or_else(truncated)?;
+    let run_length_encoded =
+        (8..0x8000).contains(&width) && header[0] == 2 && header[1] == 2 && header[2] & 0x80 == 0;
+
+    if !run_length_encoded {
+        let pixels = data.get(offset..offset + width * 4).ok_or_else(truncated)?;
+        scanline.copy_from_slice(pixels);
+        return Ok(offset + width * 4);
+    }
+
+    if ((header[2] as usize) << 8 | header[3] as usize) != width {
+        return Err("Invalid HDR: scanline width mismatch".to_string());
+    }
+    offset += 4;
+
+    // Each channel is stored separately as runs and literal spans
+    for channel in 0..4 {
+        let mut x = 0;
+        while x < width {
+            let count = *data.get(offset).ok_or_else(truncated)? as usize;
+            offset += 1;
+
+            if count > 128 {
+                let count = count - 128;
+                let value = *data.get(offset).ok_or_else(truncated)?;
+                offset += 1;
+                if x + count > width {
+                    return Err("Invalid HDR: run overflows scanline".to_string());
+                }
+                for pixel in x..x + count {
+                    scanline[pixel * 4 + channel] = value;
+                }
+                x += count;
+            } else {
+                if count == 0 || x + count > width {
+                    return Err("Invalid HDR: bad run length".to_string());
+                }
+                let values = data.get(offset..offset + count).ok_or_else(truncated)?;
+                for (idx, &value) in values.iter().enumerate() {
+                    scanline[(x + idx) * 4 + channel] = value;
+                }
+                offset += count;
+                x += count;
+            }
+        }
+    }
+
+    Ok(offset)
+}
+
+/// Map a float onto 0-255, clamping to [0, 1]
+fn to_unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,282 @@
+//! Decoders for input formats porter_texture doesn't read.
+//!
+//! JPEG, BMP, WebP and GIF files are decoded here into RGBA8 buffers, which
+//! `PorterImage::open` then wraps like any other image. Only the first frame
+//! of animated GIF and WebP files is read.
+
+use crate::porter_image::ImageBuffer;
+use std::io::Cursor;
+
+/// Input formats handled by this module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodedFormat {
+    Jpeg,
+    Bmp,
+    WebP,
+    Gif,
+}
+
+/// Decode a file's contents to RGBA8
+pub fn decode(format: DecodedFormat, data: &[u8]) -> Result<ImageBuffer, String> {
+    match format {
+        DecodedFormat::Jpeg => decode_jpeg(data),
+        DecodedFormat::Bmp => decode_bmp(data),
+        DecodedFormat::WebP => decode_webp(data),
+        DecodedFormat::Gif => decode_gif(data),
+    }
+}
+
+/// Decode a baseline or progressive JPEG
+fn decode_jpeg(data: &[u8]) -> Result<ImageBuffer, String> {
+    use jpeg_decoder::PixelFormat;
+
+    let mut decoder = jpeg_decoder::Decoder::new(Cursor::new(data));
+    let pixels = decoder.decode().map_err(|e| format!("Invalid JPEG: {e}"))?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| "Invalid JPEG: missing header".to_string())?;
+
+    let rgba: Vec<u8> = match info.pixel_format {
+        PixelFormat::L8 => pixels.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+        // 16-bit samples are big-endian; keep the high byte
+        PixelFormat::L16 => pixels
+            .chunks_exact(2)
+            .flat_map(|l| [l[0], l[0], l[0], 255])
+            .collect(),
+        PixelFormat::RGB24 => pixels
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+            .collect(),
+        PixelFormat::CMYK32 => pixels
+            .chunks_exact(4)
+            .flat_map(|cmyk| {
+                let k = 255 - cmyk[3] as u32;
+                let channel = |c: u8| ((255 - c as u32) * k / 255) as u8;
+                [channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2]), 255]
+            })
+            .collect(),
+    };
+
+    ImageBuffer::from_raw(info.width as u32, info.height as u32, rgba)
+        .ok_or_else(|| "Invalid JPEG: pixel data size mismatch".to_string())
+}
+
+/// Decode the first frame of a WebP image
+fn decode_webp(data: &[u8]) -> Result<ImageBuffer, String> {
+    let mut decoder = image_webp::WebPDecoder::new(Cursor::new(data))
+        .map_err(|e| format!("Invalid WebP: {e}"))?;
+
+    let (width, height) = decoder.dimensions();
+    let size = decoder
+        .output_buffer_size()
+        .ok_or_else(|| "WebP image is too large".to_string())?;
+    let mut pixels = vec![0u8; size];
+    decoder
+        .read_image(&mut pixels)
+        .map_err(|e| format!("Invalid WebP: {e}"))?;
+
+    let rgba = if decoder.has_alpha() {
+        pixels
+    } else {
+        pixels
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+            .collect()
+    };
+
+    ImageBuffer::from_raw(width, height, rgba)
+        .ok_or_else(|| "Invalid WebP: pixel data size mismatch".to_string())
+}
+
+/// Decode the first frame of a GIF
+///
+/// The frame is placed on a transparent canvas the size of the GIF, since a
+/// frame may cover only part of it.
+fn decode_gif(data: &[u8]) -> Result<ImageBuffer, String> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options
+        .read_info(Cursor::new(data))
+        .map_err(|e| format!("Invalid GIF: {e}"))?;
+
+    let width = decoder.width() as u32;
+    let height = decoder.height() as u32;
+    let frame = decoder
+        .read_next_frame()
+        .map_err(|e| format!("Invalid GIF: {e}"))?
+        .ok_or_else(|| "GIF has no frames".to_string())?;
+
+    let mut canvas = ImageBuffer::from_pixel(width, height, [0, 0, 0, 0]);
+    let frame_buffer = ImageBuffer::from_raw(
+        frame.width as u32,
+        frame.height as u32,
+        frame.buffer.to_vec(),
+    )
+    .ok_or_else(|| "Invalid GIF: frame size mismatch".to_string())?;
+
+    let left = frame.left as u32;
+    let top = frame.top as u32;
+    canvas.copy_region_from(
+        &frame_buffer,
+        0,
+        0,
+        (frame.width as u32).min(width.saturating_sub(left)),
+        (frame.height as u32).min(height.saturating_sub(top)),
+        left,
+        top,
+    );
+
+    Ok(canvas)
+}
+
+/// Bitmap compression methods
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+
+/// Decode an uncompressed BMP
+///
+/// Supports 8-bit paletted, 24-bit and 32-bit images, bottom-up or top-down,
+/// with 32-bit channel masks. RLE-compressed bitmaps are rejected.
+fn decode_bmp(data: &[u8]) -> Result<ImageBuffer, String> {
+    let read_u16 = |offset: usize| -> Result<u32, String> {
+        data.get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as u32)
+            .ok_or_else(|| "Invalid BMP: truncated header".to_string())
+    };
+    let read_u32 = |offset: usize| -> Result<u32, String> {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| "Invalid BMP: truncated header".to_string())
+    };
+
+    let pixel_offset = read_u32(10)? as usize;
+    let header_size = read_u32(14)? as usize;
+    if header_size < 40 {
+        return Err("Unsupported BMP: OS/2 bitmap headers are not supported".to_string());
+    }
+
+    let width = read_u32(18)? as i32;
+    let raw_height = read_u32(22)? as i32;
+    let bits_per_pixel = read_u16(28)?;
+    let compression = read_u32(30)?;
+
+    if width <= 0 || raw_height == 0 {
+        return Err("Invalid BMP: empty image".to_string());
+    }
+    let width = width as u32;
+    let height = raw_height.unsigned_abs();
+    let top_down = raw_height < 0;
+
+    // Channel masks for 32-bit images, defaulting to BGRA
+    let masks = match (bits_per_pixel, compression) {
+        (32, BI_BITFIELDS) => {
+            let alpha = if header_size >= 56 {
+                read_u32(14 + 52)?
+            } else {
+                0
+            };
+            [
+                read_u32(14 + 40)?,
+                read_u32(14 + 44)?,
+                read_u32(14 + 48)?,
+                alpha,
+            ]
+        }
+        (32, BI_RGB) => [0x00ff_0000, 0x0000_ff00, 0x0000_00ff, 0xff00_0000],
+        (8 | 24, BI_RGB) => [0; 4],
+        _ => {
+            return Err(format!(
+                "Unsupported BMP: {bits_per_pixel}-bit with compression {compression}"
+            ));
+        }
+    };
+
+    let palette: Vec<[u8; 4]> = if bits_per_pixel == 8 {
+        let colours = match read_u32(46)? {
+            0 => 256,
+            count => count.min(256) as usize,
+        };
+        let start = 14 + header_size;
+        data.get(start..start.saturating_add(colours * 4))
+            .ok_or_else(|| "Invalid BMP: truncated palette".to_string())?
+            .chunks_exact(4)
+            .map(|bgra| [bgra[2], bgra[1], bgra[0], 255])
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Sizes come from untrusted header fields, so overflow means a bad file
+    let row_size = (bits_per_pixel as usize)
+        .checked_mul(width as usize)
+        .map(|bits| bits.div_ceil(32) * 4)
+        .ok_or_else(|| "Invalid BMP: image is too large".to_string())?;
+    let pixel_end = row_size
+        .checked_mul(height as usize)
+        .and_then(|size| size.checked_add(pixel_offset))
+        .ok_or_else(|| "Invalid BMP: image is too large".to_string())?;
+    let rgba_size = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .ok_or_else(|| "Invalid BMP: image is too large".to_string())?;
+    let pixel_data = data
+        .get(pixel_offset..pixel_end)
+        .ok_or_else(|| "Invalid BMP: truncated pixel data".to_string())?;
+
+    // Images without any alpha bits set are treated as opaque
+    let has_alpha = masks[3] != 0
+        && pixel_data
+            .chunks_exact(row_size)
+            .flat_map(|row| row[..width as usize * 4].chunks_exact(4))
+            .any(|px| u32::from_le_bytes([px[0], px[1], px[2], px[3]]) & masks[3] != 0);
+
+    let mut rgba = Vec::with_capacity(rgba_size);
+    for y in 0..height as usize {
+        let source_row = if top_down { y } else { height as usize - 1 - y };
+        let row = &pixel_data[source_row * row_size..(source_row + 1) * row_size];
+
+        for x in 0..width as usize {
+            let pixel = match bits_per_pixel {
+                8 => palette
+                    .get(row[x] as usize)
+                    .copied()
+                    .unwrap_or([0, 0, 0, 255]),
+                24 => [row[x * 3 + 2], row[x * 3 + 1], row[x * 3], 255],
+                _ => {
+                    let value = u32::from_le_bytes([
+                        row[x * 4],
+                        row[x * 4 + 1],
+                        row[x * 4 + 2],
+                        row[x * 4 + 3],
+                    ]);
+                    [
+                        extract_channel(value, masks[0]),
+                        extract_channel(value, masks[1]),
+                        extract_channel(value, masks[2]),
+                        if has_alpha {
+                            extract_channel(value, masks[3])
+                        } else {
+                            255
+                        },
+                    ]
+                }
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+
+    ImageBuffer::from_raw(width, height, rgba)
+        .ok_or_else(|| "Invalid BMP: pixel data size mismatch".to_string())
+}
+
+/// Read a channel through a bit mask, scaled to 8 bits
+fn extract_channel(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+
+    let shift = mask.trailing_zeros();
+    let max = mask >> shift;
+    let channel = (value & mask) >> shift;
+    ((channel as u64 * 255 + max as u64 / 2) / max as u64) as u8
+}
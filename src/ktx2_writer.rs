@@ -0,0 +1,699 @@
+//! KTX2 texture output.
+//!
+//! Writes RGBA8 images as KTX2 containers with a full mip chain, in one of
+//! several encodings:
+//! - Uncompressed RGBA8, with a box-filtered mip chain built here
+//! - BC1, BC3 or BC7, transcoded from a UASTC encode
+//! - UASTC, for glTF `KHR_texture_basisu`
+//! - ETC1S, stored with BasisLZ supercompression
+//!
+//! The Basis Universal encoder only produces `.basis` files, so the block
+//! data, codebooks and slice layout are read back out of the `.basis` file
+//! and repacked into the KTX2 layout. Every encoding except ETC1S can also be
+//! supercompressed with Zstandard.
+
+use crate::porter_image::ImageBuffer;
+use ktx2::{
+    ColorModel, ColorPrimaries, DataFormatFlags, DfdBlockHeaderBasic, DfdHeader, Format, Header,
+    Index, LevelIndex, SampleInformation, SupercompressionScheme, TransferFunction,
+};
+use std::num::NonZeroU8;
+use std::path::Path;
+
+/// Zstandard level used for supercompression
+const ZSTD_LEVEL: i32 = 18;
+
+/// Block encoding of KTX2 output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ktx2Encoding {
+    Rgba8,
+    Bc1,
+    Bc3,
+    Bc7,
+    #[default]
+    Uastc,
+    Etc1s,
+}
+
+impl Ktx2Encoding {
+    /// Get display name for this encoding
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Ktx2Encoding::Rgba8 => "RGBA8",
+            Ktx2Encoding::Bc1 => "BC1",
+            Ktx2Encoding::Bc3 => "BC3",
+            Ktx2Encoding::Bc7 => "BC7",
+            Ktx2Encoding::Uastc => "UASTC",
+            Ktx2Encoding::Etc1s => "ETC1S",
+        }
+    }
+
+    /// Get all available encodings
+    pub const ALL: [Ktx2Encoding; 6] = [
+        Ktx2Encoding::Rgba8,
+        Ktx2Encoding::Bc1,
+        Ktx2Encoding::Bc3,
+        Ktx2Encoding::Bc7,
+        Ktx2Encoding::Uastc,
+        Ktx2Encoding::Etc1s,
+    ];
+
+    /// Whether Zstandard supercompression can be applied
+    ///
+    /// ETC1S is always stored with BasisLZ instead.
+    pub fn supports_zstd(&self) -> bool {
+        *self != Ktx2Encoding::Etc1s
+    }
+
+    /// Vulkan format of the level data; Basis formats have none
+    fn vk_format(&self, srgb: bool) -> Option<Format> {
+        let format = match (self, srgb) {
+            (Ktx2Encoding::Rgba8, false) => Format::R8G8B8A8_UNORM,
+            (Ktx2Encoding::Rgba8, true) => Format::R8G8B8A8_SRGB,
+            (Ktx2Encoding::Bc1, false) => Format::BC1_RGB_UNORM_BLOCK,
+            (Ktx2Encoding::Bc1, true) => Format::BC1_RGB_SRGB_BLOCK,
+            (Ktx2Encoding::Bc3, false) => Format::BC3_UNORM_BLOCK,
+            (Ktx2Encoding::Bc3, true) => Format::BC3_SRGB_BLOCK,
+            (Ktx2Encoding::Bc7, false) => Format::BC7_UNORM_BLOCK,
+            (Ktx2Encoding::Bc7, true) => Format::BC7_SRGB_BLOCK,
+            (Ktx2Encoding::Uastc | Ktx2Encoding::Etc1s, _) => return None,
+        };
+
+        Some(format)
+    }
+
+    /// Bytes per pixel or per 4x4 block
+    fn block_bytes(&self) -> usize {
+        match self {
+            Ktx2Encoding::Rgba8 => 4,
+            Ktx2Encoding::Bc1 | Ktx2Encoding::Etc1s => 8,
+            Ktx2Encoding::Bc3 | Ktx2Encoding::Bc7 | Ktx2Encoding::Uastc => 16,
+        }
+    }
+}
+
+impl std::fmt::Display for Ktx2Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// How KTX2 files are encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ktx2Settings {
+    pub encoding: Ktx2Encoding,
+    /// Supercompress the level data with Zstandard
+    pub zstd: bool,
+    /// Tag the texture as sRGB colour rather than linear data
+    pub srgb: bool,
+}
+
+impl Default for Ktx2Settings {
+    fn default() -> Self {
+        Self {
+            encoding: Ktx2Encoding::default(),
+            zstd: true,
+            srgb: false,
+        }
+    }
+}
+
+/// Level data ready to be written, largest level first
+struct EncodedLevels {
+    levels: Vec<Vec<u8>>,
+    /// BasisLZ global data for ETC1S
+    global_data: Option<Vec<u8>>,
+}
+
+/// Encode an RGBA8 buffer and write it as a KTX2 file
+pub fn save_ktx2(buffer: &ImageBuffer, path: &Path, settings: Ktx2Settings) -> Result<(), String> {
+    let has_alpha = buffer.as_raw().chunks_exact(4).any(|pixel| pixel[3] != 255);
+
+    let encoded = match settings.encoding {
+        Ktx2Encoding::Rgba8 => EncodedLevels {
+            levels: buffer
+                .mip_chain()
+                .into_iter()
+                .map(ImageBuffer::into_raw)
+                .collect(),
+            global_data: None,
+        },
+        Ktx2Encoding::Etc1s => {
+            let basis = encode_basis(buffer, basis_universal::BasisTextureFormat::ETC1S, settings)?;
+            etc1s_levels(&BasisFile::new(&basis)?)?
+        }
+        Ktx2Encoding::Uastc => {
+            let basis = encode_basis(
+                buffer,
+                basis_universal::BasisTextureFormat::UASTC4x4,
+                settings,
+            )?;
+            EncodedLevels {
+                levels: uastc_levels(&BasisFile::new(&basis)?)?,
+                global_data: None,
+            }
+        }
+        encoding => {
+            let basis = encode_basis(
+                buffer,
+                basis_universal::BasisTextureFormat::UASTC4x4,
+                settings,
+            )?;
+            EncodedLevels {
+                levels: transcode_levels(&basis, encoding)?,
+                global_data: None,
+            }
+        }
+    };
+
+    let data = build_container(buffer.dimensions(), encoded, settings, has_alpha)?;
+    std::fs::write(path, data).map_err(|e| format!("Failed to write KTX2: {e}"))
+}
+
+/// Encode a buffer to a `.basis` file with a generated mip chain
+fn encode_basis(
+    buffer: &ImageBuffer,
+    format: basis_universal::BasisTextureFormat,
+    settings: Ktx2Settings,
+) -> Result<Vec<u8>, String> {
+    use basis_universal::{ColorSpace, Compressor, CompressorParams};
+
+    let mut params = CompressorParams::new();
+    params.set_basis_format(format);
+    params.set_uastc_quality_level(basis_universal::UASTC_QUALITY_DEFAULT);
+    params.set_etc1s_quality_level(basis_universal::ETC1S_QUALITY_DEFAULT);
+    params.set_color_space(if settings.srgb {
+        ColorSpace::Srgb
+    } else {
+        ColorSpace::Linear
+    });
+    params.set_generate_mipmaps(true);
+    params.set_mipmap_smallest_dimension(1);
+    params.set_print_status_to_stdout(false);
+    params
+        .source_image_mut(0)
+        .init(buffer.as_raw(), buffer.width(), buffer.height(), 4);
+
+    // Outputs are already saved in parallel on the thread pool, so each
+    // encode stays on its own thread rather than spawning one per core
+    let mut compressor = Compressor::new(1);
+
+    // SAFETY: the parameters were set through the checked setters above and
+    // the source image holds a complete RGBA8 buffer
+    if !unsafe { compressor.init(&params) } {
+        return Err("Failed to initialise the Basis Universal encoder".to_string());
+    }
+    // SAFETY: the compressor was initialised with valid parameters
+    unsafe { compressor.process() }
+        .map_err(|e| format!("Basis Universal encoding failed: {e:?}"))?;
+
+    Ok(compressor.basis_file().to_vec())
+}
+
+/// Transcode every level of a UASTC `.basis` file to a BC format
+fn transcode_levels(basis: &[u8], encoding: Ktx2Encoding) -> Result<Vec<Vec<u8>>, String> {
+    use basis_universal::{TranscodeParameters, Transcoder, TranscoderTextureFormat};
+
+    let format = match encoding {
+        Ktx2Encoding::Bc1 => TranscoderTextureFormat::BC1_RGB,
+        Ktx2Encoding::Bc3 => TranscoderTextureFormat::BC3_RGBA,
+        Ktx2Encoding::Bc7 => TranscoderTextureFormat::BC7_RGBA,
+        _ => return Err(format!("{encoding} is not a transcoded format")),
+    };
+
+    let mut transcoder = Transcoder::new();
+    transcoder
+        .prepare_transcoding(basis)
+        .map_err(|_| "Failed to read the Basis Universal data".to_string())?;
+
+    let levels = (0..transcoder.image_level_count(basis, 0))
+        .map(|level_index| {
+            transcoder
+                .transcode_image_level(
+                    basis,
+                    format,
+                    TranscodeParameters {
+                        level_index,
+                        ..Default::default()
+                    },
+                )
+                .map_err(|e| {
+                    format!("Failed to transcode level {level_index} to {encoding}: {e:?}")
+                })
+        })
+        .collect();
+
+    transcoder.end_transcoding();
+    levels
+}
+
+/// Raw UASTC blocks of each level
+fn uastc_levels(basis: &BasisFile) -> Result<Vec<Vec<u8>>, String> {
+    Ok(basis
+        .slices()?
+        .into_iter()
+        .map(|slice| slice.data.to_vec())
+        .collect())
+}
+
+/// ETC1S slices of each level, with the BasisLZ global data describing them
+///
+/// Each level holds its colour slice followed by its alpha slice, if any.
+/// The global data carries the codebooks and Huffman tables shared by every
+/// level, then one image descriptor per level locating its slices.
+fn etc1s_levels(basis: &BasisFile) -> Result<EncodedLevels, String> {
+    let slices = basis.slices()?;
+    let mut levels: Vec<Vec<u8>> = Vec::new();
+    let mut image_descs = Vec::new();
+
+    for slice in slices.iter().filter(|slice| !slice.is_alpha) {
+        let alpha = slices
+            .iter()
+            .find(|other| other.is_alpha && other.level == slice.level);
+
+        let mut level = slice.data.to_vec();
+        let (alpha_offset, alpha_length) = match alpha {
+            Some(alpha) => {
+                level.extend_from_slice(alpha.data);
+                (slice.data.len() as u32, alpha.data.len() as u32)
+            }
+            None => (0, 0),
+        };
+
+        // Image flags, then the colour and alpha slice ranges within the level
+        for value in [0, 0, slice.data.len() as u32, alpha_offset, alpha_length] {
+            image_descs.extend_from_slice(&value.to_le_bytes());
+        }
+        levels.push(level);
+    }
+
+    let endpoints = basis.section(BASIS_ENDPOINTS)?;
+    let selectors = basis.section(BASIS_SELECTORS)?;
+    let tables = basis.section(BASIS_TABLES)?;
+
+    let mut global_data = Vec::new();
+    global_data.extend_from_slice(&(basis.field(BASIS_ENDPOINT_COUNT)? as u16).to_le_bytes());
+    global_data.extend_from_slice(&(basis.field(BASIS_SELECTOR_COUNT)? as u16).to_le_bytes());
+    for length in [endpoints.len(), selectors.len(), tables.len(), 0] {
+        global_data.extend_from_slice(&(length as u32).to_le_bytes());
+    }
+    global_data.extend_from_slice(&image_descs);
+    global_data.extend_from_slice(endpoints);
+    global_data.extend_from_slice(selectors);
+    global_data.extend_from_slice(tables);
+
+    Ok(EncodedLevels {
+        levels,
+        global_data: Some(global_data),
+    })
+}
+
+/// A packed little-endian `.basis` header field: byte offset and length
+type BasisField = (usize, usize);
+
+const BASIS_TOTAL_SLICES: BasisField = (14, 3);
+const BASIS_ENDPOINT_COUNT: BasisField = (39, 2);
+const BASIS_SELECTOR_COUNT: BasisField = (48, 2);
+const BASIS_SLICE_DESCS: BasisField = (65, 4);
+
+/// `.basis` sections as offset and size fields
+const BASIS_ENDPOINTS: (BasisField, BasisField) = ((41, 4), (45, 3));
+const BASIS_SELECTORS: (BasisField, BasisField) = ((50, 4), (54, 3));
+const BASIS_TABLES: (BasisField, BasisField) = ((57, 4), (61, 4));
+
+/// Size of a slice descriptor and its fields
+const SLICE_DESC_LENGTH: usize = 23;
+const SLICE_IMAGE: BasisField = (0, 3);
+const SLICE_LEVEL: BasisField = (3, 1);
+const SLICE_FLAGS: BasisField = (4, 1);
+const SLICE_OFFSET: BasisField = (13, 4);
+const SLICE_SIZE: BasisField = (17, 4);
+
+/// Slice flag marking ETC1S alpha data
+const SLICE_HAS_ALPHA: usize = 1;
+
+/// One slice of a `.basis` file
+struct BasisSlice<'a> {
+    level: usize,
+    is_alpha: bool,
+    data: &'a [u8],
+}
+
+/// A `.basis` file produced by the encoder
+struct BasisFile<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BasisFile<'a> {
+    fn new(data: &'a [u8]) -> Result<Self, String> {
+        if !data.starts_with(b"sB") {
+            return Err("Invalid Basis Universal data".to_string());
+        }
+
+        Ok(Self { data })
+    }
+
+    /// Read a header field
+    fn field(&self, field: BasisField) -> Result<usize, String> {
+        read_packed(self.data, field)
+    }
+
+    /// Bytes of a section located by header fields
+    fn section(&self, (offset, size): (BasisField, BasisField)) -> Result<&'a [u8], String> {
+        let offset = self.field(offset)?;
+        let size = self.field(size)?;
+        self.data
+            .get(offset..offset + size)
+            .ok_or_else(|| "Truncated Basis Universal data".to_string())
+    }
+
+    /// Slices of the first image, ordered by level with colour before alpha
+    fn slices(&self) -> Result<Vec<BasisSlice<'a>>, String> {
+        let count = self.field(BASIS_TOTAL_SLICES)?;
+        let start = self.field(BASIS_SLICE_DESCS)?;
+
+        let mut slices = Vec::with_capacity(count);
+        for idx in 0..count {
+            let desc = self
+                .data
+                .get(start + idx * SLICE_DESC_LENGTH..start + (idx + 1) * SLICE_DESC_LENGTH)
+                .ok_or_else(|| "Truncated Basis Universal slice table".to_string())?;
+            if read_packed(desc, SLICE_IMAGE)? != 0 {
+                continue;
+            }
+
+            let offset = read_packed(desc, SLICE_OFFSET)?;
+            let size = read_packed(desc, SLICE_SIZE)?;
+            slices.push(BasisSlice {
+                level: read_packed(desc, SLICE_LEVEL)?,
+                is_alpha: read_packed(desc, SLICE_FLAGS)? & SLICE_HAS_ALPHA != 0,
+                data: self
+                    .data
+                    .get(offset..offset + size)
+                    .ok_or_else(|| "Truncated Basis Universal slice".to_string())?,
+            });
+        }
+
+        slices.sort_by_key(|slice| (slice.level, slice.is_alpha));
+        Ok(slices)
+    }
+}
+
+/// Read a packed little-endian unsigned integer
+fn read_packed(data: &[u8], (offset, length): BasisField) -> Result<usize, String> {
+    data.get(offset..offset + length)
+        .map(|bytes| {
+            bytes
+                .iter()
+                .rev()
+                .fold(0usize, |value, &byte| (value << 8) | byte as usize)
+        })
+        .ok_or_else(|| "Truncated Basis Universal header".to_string())
+}
+
+/// Lay out the header, level index, data format descriptor and levels
+fn build_container(
+    (width, height): (u32, u32),
+    encoded: EncodedLevels,
+    settings: Ktx2Settings,
+    has_alpha: bool,
+) -> Result<Vec<u8>, String> {
+    let encoding = settings.encoding;
+    let zstd = settings.zstd && encoding.supports_zstd();
+    let scheme = match (&encoded.global_data, zstd) {
+        (Some(_), _) => Some(SupercompressionScheme::BasisLZ),
+        (None, true) => Some(SupercompressionScheme::Zstandard),
+        (None, false) => None,
+    };
+
+    let levels: Vec<(Vec<u8>, u64)> = encoded
+        .levels
+        .into_iter()
+        .map(|level| {
+            let uncompressed_length = level.len() as u64;
+            if zstd {
+                zstd::bulk::compress(&level, ZSTD_LEVEL)
+                    .map(|compressed| (compressed, uncompressed_length))
+                    .map_err(|e| format!("Zstandard compression failed: {e}"))
+            } else {
+                Ok((level, uncompressed_length))
+            }
+        })
+        .collect::<Result<_, String>>()?;
+
+    let dfd = data_format_descriptor(settings, has_alpha, scheme.is_some());
+    let dfd_offset = Header::LENGTH + LevelIndex::LENGTH * levels.len();
+    let global_data = encoded.global_data.unwrap_or_default();
+    // Global data is 8-byte aligned; supercompressed levels need no alignment
+    let global_offset = (dfd_offset + dfd.len()).next_multiple_of(8);
+    let alignment = if scheme.is_some() {
+        1
+    } else {
+        encoding.block_bytes().next_multiple_of(4)
+    };
+
+    // Levels are stored smallest first, but indexed largest first
+    let mut body = Vec::new();
+    let data_start = if global_data.is_empty() {
+        dfd_offset + dfd.len()
+    } else {
+        global_offset + global_data.len()
+    };
+    let mut level_index = vec![None; levels.len()];
+    for (level, (data, uncompressed_length)) in levels.iter().enumerate().rev() {
+        let offset = (data_start + body.len()).next_multiple_of(alignment);
+        body.resize(offset - data_start, 0);
+        body.extend_from_slice(data);
+        level_index[level] = Some(LevelIndex {
+            byte_offset: offset as u64,
+            byte_length: data.len() as u64,
+            // BasisLZ levels have no fixed uncompressed size
+            uncompressed_byte_length: match scheme {
+                Some(SupercompressionScheme::BasisLZ) => 0,
+                _ => *uncompressed_length,
+            },
+        });
+    }
+
+    let header = Header {
+        format: encoding.vk_format(settings.srgb),
+        type_size: 1,
+        pixel_width: width,
+        pixel_height: height,
+        pixel_depth: 0,
+        layer_count: 0,
+        face_count: 1,
+        level_count: levels.len() as u32,
+        supercompression_scheme: scheme,
+        index: Index {
+            dfd_byte_offset: dfd_offset as u32,
+            dfd_byte_length: dfd.len() as u32,
+            kvd_byte_offset: 0,
+            kvd_byte_length: 0,
+            sgd_byte_offset: if global_data.is_empty() {
+                0
+            } else {
+                global_offset as u64
+            },
+            sgd_byte_length: global_data.len() as u64,
+        },
+    };
+
+    let mut file = Vec::with_capacity(data_start + body.len());
+    file.extend_from_slice(&header.as_bytes());
+    for index in level_index.into_iter().flatten() {
+        file.extend_from_slice(&index.as_bytes());
+    }
+    file.extend_from_slice(&dfd);
+    if !global_data.is_empty() {
+        file.resize(global_offset, 0);
+        file.extend_from_slice(&global_data);
+    }
+    file.extend_from_slice(&body);
+
+    Ok(file)
+}
+
+/// Khronos data format channel ids
+const CHANNEL_COLOR: u8 = 0;
+const CHANNEL_GREEN: u8 = 1;
+const CHANNEL_BLUE: u8 = 2;
+const CHANNEL_UASTC_RGBA: u8 = 3;
+const CHANNEL_ALPHA: u8 = 15;
+
+/// Build the data format descriptor for an encoding
+///
+/// Supercompressed data leaves the plane sizes at zero, as the level sizes
+/// no longer follow from the block size.
+fn data_format_descriptor(
+    settings: Ktx2Settings,
+    has_alpha: bool,
+    supercompressed: bool,
+) -> Vec<u8> {
+    let encoding = settings.encoding;
+    let block = if encoding == Ktx2Encoding::Rgba8 {
+        1
+    } else {
+        4
+    };
+
+    // (bit offset, bit length, channel id) of each sample
+    let (color_model, samples): (ColorModel, Vec<(u16, u8, u8)>) = match encoding {
+        Ktx2Encoding::Rgba8 => (
+            ColorModel::RGBSDA,
+            vec![
+                (0, 8, CHANNEL_COLOR),
+                (8, 8, CHANNEL_GREEN),
+                (16, 8, CHANNEL_BLUE),
+                (24, 8, CHANNEL_ALPHA),
+            ],
+        ),
+        Ktx2Encoding::Bc1 => (ColorModel::BC1A, vec![(0, 64, CHANNEL_COLOR)]),
+        Ktx2Encoding::Bc3 => (
+            ColorModel::BC3,
+            vec![(0, 64, CHANNEL_ALPHA), (64, 64, CHANNEL_COLOR)],
+        ),
+        Ktx2Encoding::Bc7 => (ColorModel::BC7, vec![(0, 128, CHANNEL_COLOR)]),
+        Ktx2Encoding::Uastc if has_alpha => (ColorModel::UASTC, vec![(0, 128, CHANNEL_UASTC_RGBA)]),
+        Ktx2Encoding::Uastc => (ColorModel::UASTC, vec![(0, 128, CHANNEL_COLOR)]),
+        Ktx2Encoding::Etc1s if has_alpha => (
+            ColorModel::ETC1S,
+            vec![(0, 64, CHANNEL_COLOR), (64, 64, CHANNEL_ALPHA)],
+        ),
+        Ktx2Encoding::Etc1s => (ColorModel::ETC1S, vec![(0, 64, CHANNEL_COLOR)]),
+    };
+
+    let mut bytes_planes = [0u8; 8];
+    if !supercompressed {
+        bytes_planes[0] = encoding.block_bytes() as u8;
+    }
+
+    let block_header = DfdBlockHeaderBasic {
+        color_model: Some(color_model),
+        color_primaries: Some(ColorPrimaries::BT709),
+        transfer_function: Some(if settings.srgb {
+            TransferFunction::SRGB
+        } else {
+            TransferFunction::Linear
+        }),
+        flags: DataFormatFlags::STRAIGHT_ALPHA,
+        texel_block_dimensions: [block, block, 1, 1]
+            .map(|size| NonZeroU8::new(size).expect("block dimensions are non-zero")),
+        bytes_planes,
+    };
+
+    let block_size =
+        DfdHeader::LENGTH + DfdBlockHeaderBasic::LENGTH + samples.len() * SampleInformation::LENGTH;
+    let mut dfd = Vec::with_capacity(4 + block_size);
+    dfd.extend_from_slice(&((4 + block_size) as u32).to_le_bytes());
+    dfd.extend_from_slice(&DfdHeader::BASIC.as_bytes(block_size as u16));
+    dfd.extend_from_slice(&block_header.as_bytes());
+
+    for (bit_offset, bit_length, channel) in samples {
+        // Alpha is never sRGB encoded
+        let qualifiers = if channel == CHANNEL_ALPHA && settings.srgb {
+            ktx2::ChannelTypeQualifiers::LINEAR
+        } else {
+            ktx2::ChannelTypeQualifiers::empty()
+        };
+        let upper = if encoding == Ktx2Encoding::Rgba8 {
+            255
+        } else {
+            u32::MAX
+        };
+
+        let sample = SampleInformation {
+            bit_offset,
+            bit_length: NonZeroU8::new(bit_length).expect("sample lengths are non-zero"),
+            channel_type: channel,
+            channel_type_qualifiers: qualifiers,
+            sample_positions: [0; 4],
+            lower: 0,
+            upper,
+        };
+        dfd.extend_from_slice(&sample.as_bytes());
+    }
+
+    dfd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ktx2::Reader;
+
+    /// A small gradient with varying alpha
+    fn gradient(width: u32, height: u32) -> ImageBuffer {
+        let rgba = (0..height)
+            .flat_map(|y| {
+                (0..width).flat_map(move |x| {
+                    [
+                        (x * 255 / width) as u8,
+                        (y * 255 / height) as u8,
+                        128,
+                        ((x + y) * 4) as u8,
+                    ]
+                })
+            })
+            .collect();
+        ImageBuffer::from_raw(width, height, rgba).expect("buffer matches its size")
+    }
+
+    /// Write a file with the given encoding and parse it back
+    fn round_trip(encoding: Ktx2Encoding, zstd: bool) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!(
+            "ktx2_writer_{}_{zstd}_{}.ktx2",
+            encoding.display_name(),
+            std::process::id()
+        ));
+        let settings = Ktx2Settings {
+            encoding,
+            zstd,
+            srgb: true,
+        };
+
+        save_ktx2(&gradient(64, 32), &path, settings).expect("encode succeeds");
+        let data = std::fs::read(&path).expect("file was written");
+        let _ = std::fs::remove_file(&path);
+        data
+    }
+
+    #[test]
+    fn etc1s_round_trip() {
+        let data = round_trip(Ktx2Encoding::Etc1s, false);
+        let reader = Reader::new(&data[..]).expect("valid KTX2");
+        let header = reader.header();
+
+        assert_eq!((header.pixel_width, header.pixel_height), (64, 32));
+        assert_eq!(header.format, None);
+        assert_eq!(
+            header.supercompression_scheme,
+            Some(SupercompressionScheme::BasisLZ)
+        );
+        assert_eq!(header.level_count, 7);
+        assert_eq!(reader.levels().count(), 7);
+        assert!(!reader.supercompression_global_data().is_empty());
+    }
+
+    #[test]
+    fn uastc_round_trip() {
+        for zstd in [false, true] {
+            let data = round_trip(Ktx2Encoding::Uastc, zstd);
+            let reader = Reader::new(&data[..]).expect("valid KTX2");
+            let header = reader.header();
+
+            assert_eq!((header.pixel_width, header.pixel_height), (64, 32));
+            assert_eq!(header.format, None);
+            assert_eq!(
+                header.supercompression_scheme,
+                zstd.then_some(SupercompressionScheme::Zstandard)
+            );
+            assert_eq!(header.level_count, 7);
+            assert_eq!(reader.levels().count(), 7);
+
+            // 16x8 UASTC blocks of 16 bytes in the largest level
+            let largest = reader.levels().next().expect("has levels");
+            assert_eq!(largest.uncompressed_byte_length, 16 * 8 * 16);
+        }
+    }
+}
@@ -8,11 +8,16 @@ mod app_state;
 mod controller;
 mod core_logic;
 mod executor;
+mod float_images;
+mod image_decoders;
+mod ktx2_writer;
 mod logger;
 mod messages;
 mod panic_hook;
 mod porter_image;
+mod preview;
 mod settings;
+mod shortcuts;
 mod status;
 mod system;
 mod theme;
@@ -9,6 +9,10 @@ pub enum Message {
     Controller(crate::Controller),
     Main(MainMessage),
     ThemeChanged(crate::theme::AppTheme),
+    ShortcutBound(
+        crate::shortcuts::ShortcutAction,
+        crate::shortcuts::KeyBinding,
+    ),
     SettingsLoaded(crate::Settings),
     SettingsSaved,
 }
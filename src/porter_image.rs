@@ -3,7 +3,11 @@
 //! This module provides a thin wrapper around porter_texture::Image that provides
 //! a more ergonomic API similar to the image crate's DynamicImage.
 
+use crate::float_images::{self, FloatInfo, FloatRange};
+use crate::image_decoders::{self, DecodedFormat};
+use crate::ktx2_writer::{self, Ktx2Settings};
 use porter_texture::{Image, ImageFileType, ImageFormat};
+use std::io::Read;
 use std::path::Path;
 
 /// Wrapper around porter_texture::Image with ergonomic API
@@ -14,14 +18,54 @@ pub struct PorterImage {
 
 impl PorterImage {
     /// Load an image from a file path, auto-detecting the format
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+    ///
+    /// The format is detected from the file's contents, falling back to its
+    /// extension for formats without a signature (TGA). Float files outside
+    /// [0, 1] are remapped as `range` picks, with a warning logged.
+    pub fn open<P: AsRef<Path>>(path: P, range: FloatRange) -> Result<Self, String> {
         let path = path.as_ref();
-        let file_type = detect_file_type(path)?;
+        let (image, info) = Self::open_layer(path, None, range)?;
+        if let Some(remap) = info.remap {
+            tracing::warn!("{}: {}", path.display(), remap.warning());
+        }
 
-        let inner =
-            Image::load(path, file_type).map_err(|e| format!("Failed to load image: {e:?}"))?;
+        Ok(image)
+    }
 
-        Ok(Self { inner })
+    /// Load an image, reading the named layer if it is a multi-layer EXR
+    ///
+    /// `layer` is ignored for other formats. EXR files also return the
+    /// layers they offer; None picks the first colour layer. Float files
+    /// report how their values were remapped into [0, 1], if they were.
+    pub fn open_layer<P: AsRef<Path>>(
+        path: P,
+        layer: Option<&str>,
+        range: FloatRange,
+    ) -> Result<(Self, FloatInfo), String> {
+        let path = path.as_ref();
+        let read =
+            || std::fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()));
+
+        let image = match detect_source_format(path)? {
+            SourceFormat::Porter(file_type) => {
+                let inner = Image::load(path, file_type)
+                    .map_err(|e| format!("Failed to load image: {e:?}"))?;
+                Self { inner }
+            }
+            SourceFormat::Decoded(format) => {
+                image_decoders::decode(format, &read()?)?.into_porter_image()?
+            }
+            SourceFormat::Hdr => {
+                let (buffer, info) = float_images::decode_hdr(&read()?, range)?;
+                return Ok((buffer.into_linear_porter_image()?, info));
+            }
+            SourceFormat::Exr => {
+                let (buffer, info) = float_images::decode_exr(&read()?, layer, range)?;
+                return Ok((buffer.into_linear_porter_image()?, info));
+            }
+        };
+
+        Ok((image, FloatInfo::default()))
     }
 
     /// Create a new image with the given dimensions and format
@@ -37,6 +81,98 @@ impl PorterImage {
         (self.inner.width(), self.inner.height())
     }
 
+    /// Number of frames: cubemap faces or array slices
+    pub fn frame_count(&self) -> usize {
+        self.inner.frames().len()
+    }
+
+    /// Number of mip levels in each frame
+    pub fn mip_count(&self) -> u32 {
+        self.inner.mipmaps()
+    }
+
+    /// Whether the frames are the six faces of a cubemap
+    pub fn is_cubemap(&self) -> bool {
+        self.inner.is_cubemap()
+    }
+
+    /// Copy one frame, with its mip chain, into an image of its own
+    pub fn frame(&self, index: usize) -> Result<Self, String> {
+        let source = self
+            .inner
+            .frames()
+            .get(index)
+            .ok_or_else(|| format!("Image has no frame {index}"))?;
+
+        let mut inner =
+            Image::with_mipmaps(self.width(), self.height(), self.mip_count(), self.format())
+                .map_err(|e| format!("Failed to create image: {e:?}"))?;
+        inner
+            .create_frame()
+            .map_err(|e| format!("Failed to create frame: {e:?}"))?
+            .buffer_mut()
+            .copy_from_slice(source.buffer());
+
+        Ok(Self { inner })
+    }
+
+    /// Build a multi-frame image from RGBA8 frames of the same size
+    ///
+    /// With `mipmaps` set each frame gets a full mip chain, stored after the
+    /// frame's top level. With `cubemap` set the frames are saved as the six
+    /// faces of a cubemap.
+    pub fn from_frames(
+        frames: &[ImageBuffer],
+        mipmaps: bool,
+        cubemap: bool,
+    ) -> Result<Self, String> {
+        let first = frames
+            .first()
+            .ok_or_else(|| "No frames to combine".to_string())?;
+        if frames
+            .iter()
+            .any(|frame| frame.dimensions() != first.dimensions())
+        {
+            return Err("Frames differ in size".to_string());
+        }
+        if cubemap && frames.len() != 6 {
+            return Err(format!("A cubemap needs 6 faces, not {}", frames.len()));
+        }
+
+        let chains: Vec<Vec<ImageBuffer>> = frames
+            .iter()
+            .map(|frame| {
+                if mipmaps {
+                    frame.mip_chain()
+                } else {
+                    vec![frame.clone()]
+                }
+            })
+            .collect();
+
+        let mut inner = Image::with_mipmaps(
+            first.width(),
+            first.height(),
+            chains[0].len() as u32,
+            ImageFormat::R8G8B8A8UnormSrgb,
+        )
+        .map_err(|e| format!("Failed to create image: {e:?}"))?;
+
+        for chain in chains {
+            let data: Vec<u8> = chain.into_iter().flat_map(ImageBuffer::into_raw).collect();
+            let frame = inner
+                .create_frame()
+                .map_err(|e| format!("Failed to create frame: {e:?}"))?;
+            if frame.buffer().len() != data.len() {
+                return Err("Frame size doesn't match its mip chain".to_string());
+            }
+            frame.buffer_mut().copy_from_slice(&data);
+        }
+        inner.set_cubemap(cubemap);
+
+        Ok(Self { inner })
+    }
+
     /// Get the width of the image
     pub fn width(&self) -> u32 {
         self.inner.width()
@@ -84,6 +220,24 @@ impl PorterImage {
         Ok(frame.buffer())
     }
 
+    /// Copy the top level of the first frame into a tightly packed RGBA8
+    /// buffer
+    ///
+    /// Converts a copy of the image; the original is left untouched.
+    pub fn to_rgba8_image_buffer(&self) -> Result<ImageBuffer, String> {
+        let (width, height) = self.dimensions();
+        let mut img = self.clone();
+        // Lower mip levels follow the top level in the frame
+        let data = img
+            .as_rgba8_buffer()?
+            .get(..width as usize * height as usize * 4)
+            .ok_or_else(|| "Buffer length mismatch".to_string())?
+            .to_vec();
+
+        ImageBuffer::from_raw(width, height, data)
+            .ok_or_else(|| "Buffer length mismatch".to_string())
+    }
+
     /// Get the raw buffer without conversion (first frame)
     pub fn raw_buffer(&self) -> Result<&[u8], String> {
         let frame = self
@@ -119,8 +273,30 @@ impl PorterImage {
     }
 
     /// Save the image to a file
-    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+    pub fn save<P: AsRef<Path>>(&mut self, path: P, options: &SaveOptions) -> Result<(), String> {
         let path = path.as_ref();
+
+        // porter_texture can't write EXR or KTX2, so they are encoded from
+        // RGBA8 here. Both writers take a single 2D image, so only the first
+        // face or slice of a multi-frame image is kept.
+        let single_image = has_extension(path, "exr") || has_extension(path, "ktx2");
+        if single_image && self.frame_count() > 1 {
+            tracing::warn!(
+                "{} holds a single image, saving only the first of {} frames",
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or_default()
+                    .to_uppercase(),
+                self.frame_count()
+            );
+        }
+        if has_extension(path, "exr") {
+            return float_images::save_exr(&self.to_rgba8_image_buffer()?, path);
+        }
+        if has_extension(path, "ktx2") {
+            return ktx2_writer::save_ktx2(&self.to_rgba8_image_buffer()?, path, options.ktx2);
+        }
+
         let file_type = detect_file_type(path)?;
 
         let best_format = self.inner.format_for_file_type(file_type);
@@ -133,6 +309,37 @@ impl PorterImage {
             .map_err(|e| format!("Failed to save image: {e:?}"))
     }
 
+    /// Save to a file without ever leaving a partial file at `path`
+    ///
+    /// The image is written to a temporary file in the same folder and then
+    /// renamed over the destination, so a failed save leaves any existing
+    /// file untouched.
+    pub fn save_atomic<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        options: &SaveOptions,
+    ) -> Result<(), String> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| format!("Invalid file name: {}", path.display()))?;
+
+        // Keep the extension last so the file type is still detected
+        let temp_path = path.with_file_name(format!(".{}.{file_name}", std::process::id()));
+
+        let result = self.save(&temp_path, options).and_then(|_| {
+            std::fs::rename(&temp_path, path)
+                .map_err(|e| format!("Failed to replace {}: {e}", path.display()))
+        });
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+
+        result
+    }
+
     /// Get a reference to the inner porter_texture::Image
     pub fn inner(&self) -> &Image {
         &self.inner
@@ -163,6 +370,12 @@ impl PorterImage {
     }
 }
 
+/// Encoder settings for formats that have them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveOptions {
+    pub ktx2: Ktx2Settings,
+}
+
 /// RGBA8 image buffer wrapper
 #[derive(Debug, Clone)]
 pub struct ImageBuffer {
@@ -174,7 +387,7 @@ pub struct ImageBuffer {
 impl ImageBuffer {
     /// Create a new image buffer from dimensions and data
     pub fn from_raw(width: u32, height: u32, data: Vec<u8>) -> Option<Self> {
-        let expected_size = (width * height * 4) as usize;
+        let expected_size = width as usize * height as usize * 4;
         if data.len() != expected_size {
             return None;
         }
@@ -188,12 +401,7 @@ impl ImageBuffer {
 
     /// Create a new image buffer with all pixels set to a colour
     pub fn from_pixel(width: u32, height: u32, pixel: [u8; 4]) -> Self {
-        let size = (width * height * 4) as usize;
-        let mut data = Vec::with_capacity(size);
-
-        for _ in 0..(width * height) {
-            data.extend_from_slice(&pixel);
-        }
+        let data = pixel.repeat(width as usize * height as usize);
 
         Self {
             width,
@@ -227,9 +435,73 @@ impl ImageBuffer {
         &mut self.data
     }
 
+    /// Build the full mip chain down to 1x1, starting with this buffer
+    pub fn mip_chain(&self) -> Vec<ImageBuffer> {
+        let mut levels = vec![self.clone()];
+
+        while let Some(last) = levels
+            .last()
+            .filter(|last| last.width > 1 || last.height > 1)
+        {
+            let next = last.next_mip();
+            levels.push(next);
+        }
+
+        levels
+    }
+
+    /// Halve the buffer with a 2x2 box filter, rounding dimensions down
+    ///
+    /// Mip level sizes are `max(1, size >> level)`, so odd edges drop their
+    /// last row or column.
+    fn next_mip(&self) -> ImageBuffer {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mip_width = (width / 2).max(1);
+        let mip_height = (height / 2).max(1);
+
+        let src = &self.data;
+        let stride = width * 4;
+        let mut data = vec![0u8; mip_width * mip_height * 4];
+
+        for y in 0..mip_height {
+            let y0 = (y * 2).min(height - 1);
+            let y1 = (y * 2 + 1).min(height - 1);
+
+            for x in 0..mip_width {
+                let x0 = (x * 2).min(width - 1);
+                let x1 = (x * 2 + 1).min(width - 1);
+
+                let dst = (y * mip_width + x) * 4;
+                for c in 0..4 {
+                    let sum = src[y0 * stride + x0 * 4 + c] as u32
+                        + src[y0 * stride + x1 * 4 + c] as u32
+                        + src[y1 * stride + x0 * 4 + c] as u32
+                        + src[y1 * stride + x1 * 4 + c] as u32;
+                    data[dst + c] = ((sum + 2) / 4) as u8;
+                }
+            }
+        }
+
+        ImageBuffer {
+            width: mip_width as u32,
+            height: mip_height as u32,
+            data,
+        }
+    }
+
     /// Convert to a PorterImage (uses sRGB format for display)
     pub fn into_porter_image(self) -> Result<PorterImage, String> {
-        let mut image = Image::new(self.width, self.height, ImageFormat::R8G8B8A8UnormSrgb)
+        self.into_porter_image_as(ImageFormat::R8G8B8A8UnormSrgb)
+    }
+
+    /// Convert to a PorterImage holding linear data, such as decoded EXR values
+    pub fn into_linear_porter_image(self) -> Result<PorterImage, String> {
+        self.into_porter_image_as(ImageFormat::R8G8B8A8Unorm)
+    }
+
+    /// Convert to a PorterImage tagged with an RGBA8 format
+    fn into_porter_image_as(self, format: ImageFormat) -> Result<PorterImage, String> {
+        let mut image = Image::new(self.width, self.height, format)
             .map_err(|e| format!("Failed to create image: {e:?}"))?;
 
         let frame = image
@@ -241,6 +513,13 @@ impl ImageBuffer {
         Ok(PorterImage::from_inner(image))
     }
 
+    /// Copy resized to new dimensions, treating the data as linear
+    pub fn resized(&self, width: u32, height: u32) -> Result<ImageBuffer, String> {
+        let mut image = self.clone().into_linear_porter_image()?;
+        image.resize(width, height)?;
+        image.to_rgba8_image_buffer()
+    }
+
     /// Get a pixel at the given coordinates
     pub fn get_pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
         if x >= self.width || y >= self.height {
@@ -279,12 +558,172 @@ impl ImageBuffer {
             })
     }
 
+    /// Copy a rectangular region into a new image buffer
+    ///
+    /// The region is clamped to the bounds of this buffer.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+
+        let row_bytes = width as usize * 4;
+        let mut data = Vec::with_capacity(row_bytes * height as usize);
+
+        for row in y..y + height {
+            let offset = (row as usize * self.width as usize + x as usize) * 4;
+            data.extend_from_slice(&self.data[offset..offset + row_bytes]);
+        }
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Copy a rectangular region of another buffer into this one
+    ///
+    /// Copies `width`x`height` texels from `(src_x, src_y)` in `source` to
+    /// `(dst_x, dst_y)` in this buffer. Texels outside either buffer are skipped.
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy_region_from(
+        &mut self,
+        source: &ImageBuffer,
+        src_x: u32,
+        src_y: u32,
+        width: u32,
+        height: u32,
+        dst_x: u32,
+        dst_y: u32,
+    ) {
+        let width = width
+            .min(source.width.saturating_sub(src_x))
+            .min(self.width.saturating_sub(dst_x));
+        let height = height
+            .min(source.height.saturating_sub(src_y))
+            .min(self.height.saturating_sub(dst_y));
+
+        let row_bytes = width as usize * 4;
+
+        for row in 0..height {
+            let src_offset = ((src_y + row) as usize * source.width as usize + src_x as usize) * 4;
+            let dst_offset = ((dst_y + row) as usize * self.width as usize + dst_x as usize) * 4;
+
+            self.data[dst_offset..dst_offset + row_bytes]
+                .copy_from_slice(&source.data[src_offset..src_offset + row_bytes]);
+        }
+    }
+
     /// Clone the data into a new Vec
     pub fn into_raw(self) -> Vec<u8> {
         self.data
     }
 }
 
+/// Whether a file has an extension that can be loaded
+pub fn is_supported_image(path: &Path) -> bool {
+    source_format_from_extension(path).is_ok()
+}
+
+/// How an input file is decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceFormat {
+    /// Loaded by porter_texture
+    Porter(ImageFileType),
+    /// Decoded to RGBA8 by `image_decoders`
+    Decoded(DecodedFormat),
+    /// OpenEXR, decoded by `float_images`
+    Exr,
+    /// Radiance RGBE, decoded by `float_images`
+    Hdr,
+}
+
+/// Detect an input file's format from its signature, then its extension
+fn detect_source_format(path: &Path) -> Result<SourceFormat, String> {
+    let mut header = [0u8; 12];
+    let read = std::fs::File::open(path)
+        .and_then(|mut file| file.read(&mut header))
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+    match source_format_from_signature(&header[..read]) {
+        Some(format) => Ok(format),
+        None => source_format_from_extension(path),
+    }
+}
+
+/// Match the leading bytes of a file against known signatures
+fn source_format_from_signature(header: &[u8]) -> Option<SourceFormat> {
+    let format = match header {
+        [0x89, b'P', b'N', b'G', ..] => SourceFormat::Porter(ImageFileType::Png),
+        [b'D', b'D', b'S', b' ', ..] => SourceFormat::Porter(ImageFileType::Dds),
+        [b'I', b'I', 0x2a, 0x00, ..] | [b'M', b'M', 0x00, 0x2a, ..] => {
+            SourceFormat::Porter(ImageFileType::Tiff)
+        }
+        [0xff, 0xd8, 0xff, ..] => SourceFormat::Decoded(DecodedFormat::Jpeg),
+        [b'G', b'I', b'F', b'8', ..] => SourceFormat::Decoded(DecodedFormat::Gif),
+        [
+            b'R',
+            b'I',
+            b'F',
+            b'F',
+            _,
+            _,
+            _,
+            _,
+            b'W',
+            b'E',
+            b'B',
+            b'P',
+            ..,
+        ] => SourceFormat::Decoded(DecodedFormat::WebP),
+        [b'B', b'M', ..] => SourceFormat::Decoded(DecodedFormat::Bmp),
+        [0x76, 0x2f, 0x31, 0x01, ..] => SourceFormat::Exr,
+        [
+            b'#',
+            b'?',
+            b'R',
+            b'A',
+            b'D',
+            b'I',
+            b'A',
+            b'N',
+            b'C',
+            b'E',
+            ..,
+        ]
+        | [b'#', b'?', b'R', b'G', b'B', b'E', ..] => SourceFormat::Hdr,
+        _ => return None,
+    };
+
+    Some(format)
+}
+
+/// Detect an input file's format from its extension
+fn source_format_from_extension(path: &Path) -> Result<SourceFormat, String> {
+    let extension = path
+        .extension()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| format!("No file extension found for: {}", path.display()))?;
+
+    match extension.to_lowercase().as_str() {
+        "jpg" | "jpeg" => Ok(SourceFormat::Decoded(DecodedFormat::Jpeg)),
+        "bmp" => Ok(SourceFormat::Decoded(DecodedFormat::Bmp)),
+        "webp" => Ok(SourceFormat::Decoded(DecodedFormat::WebP)),
+        "gif" => Ok(SourceFormat::Decoded(DecodedFormat::Gif)),
+        "exr" => Ok(SourceFormat::Exr),
+        "hdr" => Ok(SourceFormat::Hdr),
+        _ => detect_file_type(path).map(SourceFormat::Porter),
+    }
+}
+
+/// Whether a path has the given extension, ignoring case
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
 /// Detect file type from file extension
 fn detect_file_type(path: &Path) -> Result<ImageFileType, String> {
     let extension = path
@@ -0,0 +1,135 @@
+//! Downscaled preview pyramids for display.
+//!
+//! Full-resolution images can be far larger than anything the UI needs to show
+//! (or than the renderer accepts), so display handles are built from a chain of
+//! box-filtered copies. Each level keeps its pixels so display transforms can
+//! be applied without touching the full-resolution data, which stays with the
+//! caller for processing and saving.
+
+use crate::porter_image::ImageBuffer;
+use iced::widget::image::Handle;
+use std::sync::Arc;
+
+/// Largest dimension of the biggest preview level
+pub const PREVIEW_MAX_DIMENSION: u32 = 2048;
+
+/// Levels stop once both dimensions are at or below this size
+const PREVIEW_MIN_DIMENSION: u32 = 256;
+
+/// A single level of a preview pyramid
+#[derive(Debug, Clone)]
+struct PreviewLevel {
+    buffer: ImageBuffer,
+    handle: Handle,
+}
+
+/// A chain of progressively halved display handles for one image
+///
+/// Levels are shared, so cloning a pyramid is cheap.
+#[derive(Debug, Clone)]
+pub struct PreviewPyramid {
+    /// Largest level first
+    levels: Arc<[PreviewLevel]>,
+}
+
+impl PreviewPyramid {
+    /// Build a preview pyramid from an RGBA8 buffer
+    ///
+    /// This can take a while for very large buffers and should be called
+    /// from a background task rather than the UI thread.
+    pub fn from_buffer(buffer: &ImageBuffer) -> Self {
+        // Halve until the image fits the largest preview level
+        let mut scaled: Option<ImageBuffer> = None;
+        loop {
+            let current = scaled.as_ref().unwrap_or(buffer);
+            if current.width().max(current.height()) <= PREVIEW_MAX_DIMENSION {
+                break;
+            }
+            scaled = Some(downsample_half(current));
+        }
+
+        // Keep halving for smaller display sizes
+        let mut levels = Vec::new();
+        loop {
+            let current = scaled.as_ref().unwrap_or(buffer);
+            levels.push(PreviewLevel::new(current));
+            if current.width().max(current.height()) <= PREVIEW_MIN_DIMENSION {
+                break;
+            }
+            scaled = Some(downsample_half(current));
+        }
+
+        Self {
+            levels: levels.into(),
+        }
+    }
+
+    /// Get the best handle for displaying at the given size
+    ///
+    /// Picks the smallest level that still covers `size` logical pixels,
+    /// falling back to the largest level.
+    pub fn handle_for_size(&self, size: f32) -> &Handle {
+        &self.level_for_size(size).handle
+    }
+
+    /// Get the best pixels for displaying at the given size
+    ///
+    /// Uses the same level as `handle_for_size`.
+    pub fn buffer_for_size(&self, size: f32) -> &ImageBuffer {
+        &self.level_for_size(size).buffer
+    }
+
+    /// Smallest level that still covers `size`, or the largest level
+    fn level_for_size(&self, size: f32) -> &PreviewLevel {
+        self.levels
+            .iter()
+            .rev()
+            .find(|level| level.buffer.width().max(level.buffer.height()) as f32 >= size)
+            .unwrap_or(&self.levels[0])
+    }
+}
+
+impl PreviewLevel {
+    /// Create a level and its display handle from a buffer
+    fn new(buffer: &ImageBuffer) -> Self {
+        Self {
+            buffer: buffer.clone(),
+            handle: crate::core_logic::buffer_to_handle(buffer),
+        }
+    }
+}
+
+/// Halve a buffer in both dimensions with a 2x2 box filter
+///
+/// Odd edges clamp to the last row/column. Dimensions never go below 1.
+fn downsample_half(buffer: &ImageBuffer) -> ImageBuffer {
+    let (width, height) = buffer.dimensions();
+    let half_width = width.div_ceil(2).max(1);
+    let half_height = height.div_ceil(2).max(1);
+
+    let src = buffer.as_raw();
+    let stride = width as usize * 4;
+    let mut data = vec![0u8; half_width as usize * half_height as usize * 4];
+
+    for y in 0..half_height as usize {
+        let y0 = (y * 2).min(height as usize - 1);
+        let y1 = (y * 2 + 1).min(height as usize - 1);
+
+        for x in 0..half_width as usize {
+            let x0 = (x * 2).min(width as usize - 1);
+            let x1 = (x * 2 + 1).min(width as usize - 1);
+
+            let dst = (y * half_width as usize + x) * 4;
+            for c in 0..4 {
+                let sum = src[y0 * stride + x0 * 4 + c] as u32
+                    + src[y0 * stride + x1 * 4 + c] as u32
+                    + src[y1 * stride + x0 * 4 + c] as u32
+                    + src[y1 * stride + x1 * 4 + c] as u32;
+                data[dst + c] = ((sum + 2) / 4) as u8;
+            }
+        }
+    }
+
+    ImageBuffer::from_raw(half_width, half_height, data)
+        .expect("downsampled buffer has matching dimensions")
+}
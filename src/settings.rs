@@ -1,10 +1,21 @@
+use crate::shortcuts::Shortcuts;
 use crate::theme::AppTheme;
 use bincode::{Decode, Encode};
 use directories::ProjectDirs;
 
+/// Layout version written before the settings, bumped whenever fields change
+const SETTINGS_VERSION: u32 = 2;
+
 #[derive(Debug, Clone, Encode, Decode, Default)]
 pub struct Settings {
     pub theme: AppTheme,
+    pub shortcuts: Shortcuts,
+}
+
+/// Settings as saved before they carried a version, holding only the theme
+#[derive(Decode)]
+struct UnversionedSettings {
+    theme: AppTheme,
 }
 
 impl Settings {
@@ -14,16 +25,27 @@ impl Settings {
             return Default::default();
         };
 
-        std::fs::read(project_directory.config_dir().join("settings.dat")).map_or(
-            Default::default(),
-            |buffer| {
-                let config = bincode::config::standard();
+        std::fs::read(project_directory.config_dir().join("settings.dat"))
+            .map_or(Default::default(), |buffer| Self::decode(&buffer))
+    }
+
+    /// Decodes saved settings, upgrading files written before versioning
+    fn decode(buffer: &[u8]) -> Self {
+        let config = bincode::config::standard();
+
+        if let Ok(((SETTINGS_VERSION, settings), _)) =
+            bincode::decode_from_slice::<(u32, Settings), _>(buffer, config)
+        {
+            return settings;
+        }
 
-                bincode::decode_from_slice(&buffer, config)
-                    .unwrap_or_default()
-                    .0
+        match bincode::decode_from_slice::<UnversionedSettings, _>(buffer, config) {
+            Ok((old, read)) if read == buffer.len() => Self {
+                theme: old.theme,
+                ..Default::default()
             },
-        )
+            _ => Default::default(),
+        }
     }
 
     /// Saves settings to disk
@@ -34,7 +56,7 @@ impl Settings {
 
         let config = bincode::config::standard();
 
-        let Ok(result) = bincode::encode_to_vec(self, config) else {
+        let Ok(result) = bincode::encode_to_vec((SETTINGS_VERSION, self), config) else {
             return;
         };
 
@@ -0,0 +1,201 @@
+use bincode::{Decode, Encode};
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+use std::fmt;
+
+/// An action that can be bound to a key.
+///
+/// Saved by position, so new actions must be added at the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum ShortcutAction {
+    BrowseFocusedSlot,
+    SaveAll,
+    SaveCurrent,
+    NextOutput,
+    PreviousOutput,
+    ReloadShaders,
+    Clear,
+    Undo,
+    Redo,
+    CommandPalette,
+}
+
+impl ShortcutAction {
+    pub const ALL: &'static [ShortcutAction] = &[
+        ShortcutAction::BrowseFocusedSlot,
+        ShortcutAction::SaveAll,
+        ShortcutAction::SaveCurrent,
+        ShortcutAction::NextOutput,
+        ShortcutAction::PreviousOutput,
+        ShortcutAction::ReloadShaders,
+        ShortcutAction::Clear,
+        ShortcutAction::Undo,
+        ShortcutAction::Redo,
+        ShortcutAction::CommandPalette,
+    ];
+
+    /// Name shown in the command palette.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::BrowseFocusedSlot => "Browse for focused slot",
+            ShortcutAction::SaveAll => "Save all outputs",
+            ShortcutAction::SaveCurrent => "Save current output",
+            ShortcutAction::NextOutput => "Next output",
+            ShortcutAction::PreviousOutput => "Previous output",
+            ShortcutAction::ReloadShaders => "Reload shaders",
+            ShortcutAction::Clear => "Clear all images",
+            ShortcutAction::Undo => "Undo",
+            ShortcutAction::Redo => "Redo",
+            ShortcutAction::CommandPalette => "Command palette",
+        }
+    }
+}
+
+/// A key combined with modifiers.
+///
+/// Character keys are stored lowercase, named keys by their name (e.g. `Delete`).
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct KeyBinding {
+    pub key: String,
+    pub command: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    /// Constructs a binding for a character key.
+    fn character(key: &str, command: bool, shift: bool) -> Self {
+        Self {
+            key: key.to_string(),
+            command,
+            shift,
+            alt: false,
+        }
+    }
+
+    /// Constructs a binding for a named key without modifiers.
+    fn named(key: Named) -> Self {
+        Self {
+            key: format!("{key:?}"),
+            command: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Constructs a binding from a key press, ignoring lone modifier keys.
+    pub fn from_key(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        let key = match key.as_ref() {
+            Key::Named(
+                Named::Control
+                | Named::Shift
+                | Named::Alt
+                | Named::Super
+                | Named::Meta
+                | Named::Hyper,
+            ) => return None,
+            Key::Named(named) => format!("{named:?}"),
+            Key::Character(c) => c.to_lowercase(),
+            Key::Unidentified => return None,
+        };
+
+        Some(Self {
+            key,
+            command: modifiers.command(),
+            shift: modifiers.shift(),
+            alt: modifiers.alt(),
+        })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+
+        if self.key.chars().count() == 1 {
+            write!(f, "{}", self.key.to_uppercase())
+        } else {
+            write!(f, "{}", self.key)
+        }
+    }
+}
+
+/// Key bindings for every shortcut action.
+///
+/// An action may have several bindings, but a key combination maps to one action.
+/// Only the bindings the user chose are saved; every other action keeps its
+/// default, so actions added later are bound without resetting the rest.
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct Shortcuts {
+    custom: Vec<(ShortcutAction, KeyBinding)>,
+}
+
+impl Shortcuts {
+    /// Every binding in effect: the user's, then the defaults they leave alone.
+    pub fn bindings(&self) -> Vec<(ShortcutAction, KeyBinding)> {
+        let defaults = default_bindings().into_iter().filter(|(action, binding)| {
+            !self
+                .custom
+                .iter()
+                .any(|(custom, existing)| custom == action || existing == binding)
+        });
+
+        self.custom.iter().cloned().chain(defaults).collect()
+    }
+
+    /// Finds the action bound to a key press.
+    pub fn action_for(&self, key: &Key, modifiers: Modifiers) -> Option<ShortcutAction> {
+        let pressed = KeyBinding::from_key(key, modifiers)?;
+
+        self.bindings()
+            .into_iter()
+            .find(|(_, binding)| *binding == pressed)
+            .map(|(action, _)| action)
+    }
+
+    /// Describes the bindings for an action, e.g. `Ctrl+Y, Ctrl+Shift+Z`.
+    pub fn describe(&self, action: ShortcutAction) -> String {
+        self.bindings()
+            .iter()
+            .filter(|(bound, _)| *bound == action)
+            .map(|(_, binding)| binding.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Replaces the bindings for an action.
+    ///
+    /// The key combination is removed from any other action first.
+    pub fn rebind(&mut self, action: ShortcutAction, binding: KeyBinding) {
+        self.custom
+            .retain(|(bound, existing)| *bound != action && *existing != binding);
+        self.custom.push((action, binding));
+    }
+}
+
+/// Bindings for actions the user hasn't rebound.
+fn default_bindings() -> Vec<(ShortcutAction, KeyBinding)> {
+    use ShortcutAction::*;
+
+    vec![
+        (BrowseFocusedSlot, KeyBinding::character("o", true, false)),
+        (SaveAll, KeyBinding::character("s", true, false)),
+        (SaveCurrent, KeyBinding::character("s", true, true)),
+        (NextOutput, KeyBinding::named(Named::ArrowRight)),
+        (PreviousOutput, KeyBinding::named(Named::ArrowLeft)),
+        (ReloadShaders, KeyBinding::character("r", true, false)),
+        (Clear, KeyBinding::named(Named::Delete)),
+        (Undo, KeyBinding::character("z", true, false)),
+        (Redo, KeyBinding::character("y", true, false)),
+        (Redo, KeyBinding::character("z", true, true)),
+        (CommandPalette, KeyBinding::character("p", true, false)),
+    ]
+}
@@ -5,7 +5,9 @@ use iced::keyboard;
 use iced::keyboard::Modifiers;
 
 use iced::widget::container;
-use iced::widget::{button, column, horizontal_rule, pick_list, row, text};
+use iced::widget::{
+    button, column, horizontal_rule, mouse_area, opaque, pick_list, row, stack, text,
+};
 
 use iced::window;
 use iced::window::Mode;
@@ -22,7 +24,15 @@ use iced::Task;
 use crate::AppState;
 use crate::Message;
 use crate::components::about::About;
+use crate::components::command_palette::{CommandPalette, PaletteEntry};
 use crate::components::texture_converter::{TextureSplitter, TextureSplitterMessage};
+use crate::shortcuts::{KeyBinding, ShortcutAction};
+
+/// Time to wait for more files after a drop before handling them (milliseconds)
+///
+/// A multi-file drop arrives as one event per file, so drops are collected
+/// until they stop arriving.
+const DROP_SETTLE_MS: u64 = 50;
 
 /// Main window handler.
 pub struct MainWindow {
@@ -30,6 +40,8 @@ pub struct MainWindow {
     pub texture_splitter: TextureSplitter,
     pub about: About,
     pub show_about: bool,
+    pub palette: CommandPalette,
+    drop_generation: u64,
 }
 
 /// Messages produced by the main window.
@@ -41,6 +53,10 @@ pub enum MainMessage {
     ShowAbout,
     HideAbout,
     FontLoaded(Result<(), iced::font::Error>),
+    PaletteEntrySelected(PaletteEntry),
+    PaletteRebind(ShortcutAction),
+    PaletteClose,
+    DropsSettled(u64),
 }
 
 impl MainWindow {
@@ -64,6 +80,8 @@ impl MainWindow {
                 texture_splitter: TextureSplitter::new(),
                 about: About::new(),
                 show_about: false,
+                palette: CommandPalette::new(),
+                drop_generation: 0,
             },
             task,
         )
@@ -96,6 +114,16 @@ impl MainWindow {
                 }
                 Task::none()
             }
+            PaletteEntrySelected(entry) => self.on_palette_entry(entry),
+            PaletteRebind(action) => {
+                self.palette.capturing = Some(action);
+                Task::none()
+            }
+            PaletteClose => {
+                self.palette.hide();
+                Task::none()
+            }
+            DropsSettled(generation) => self.on_drops_settled(state, generation),
         }
     }
 
@@ -153,11 +181,30 @@ impl MainWindow {
         .width(Length::Fill)
         .height(Length::Fill);
 
-        container(content)
+        let content = container(content)
             .style(main_background_style)
             .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+            .height(Length::Fill);
+
+        if !self.palette.open {
+            return content.into();
+        }
+
+        let shader_names = self.texture_splitter.shader_names();
+        let palette = self.palette.view(&shader_names, &state.settings.shortcuts);
+
+        // Clicking outside the palette closes it
+        let backdrop = mouse_area(
+            container(opaque(palette))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding([80, 0])
+                .center_x(Length::Fill)
+                .style(palette_backdrop_style),
+        )
+        .on_press(Message::Main(MainMessage::PaletteClose));
+
+        stack![content, opaque(backdrop)].into()
     }
 
     /// Occurs when a ui event has fired.
@@ -166,6 +213,12 @@ impl MainWindow {
             Event::Keyboard(keyboard::Event::ModifiersChanged(modifier_keys)) => {
                 self.on_modifiers_changed(state, modifier_keys)
             }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key,
+                modifiers,
+                text,
+                ..
+            }) => self.on_key_pressed(state, key, modifiers, text.as_deref()),
             Event::Window(window::Event::Opened { .. }) => self.on_opened(),
             Event::Window(window::Event::Closed) => self.on_closed(),
             Event::Window(window::Event::FileHovered(_)) => self.on_file_hovered(state),
@@ -185,6 +238,121 @@ impl MainWindow {
         Task::none()
     }
 
+    /// Occurs when a key is pressed.
+    fn on_key_pressed(
+        &mut self,
+        state: &mut AppState,
+        key: keyboard::Key,
+        modifiers: Modifiers,
+        text: Option<&str>,
+    ) -> Task<Message> {
+        use keyboard::Key;
+        use keyboard::key::Named;
+
+        // A rebind in progress takes the next key combination
+        if let Some(action) = self.palette.capturing {
+            if matches!(key.as_ref(), Key::Named(Named::Escape)) {
+                self.palette.capturing = None;
+                return Task::none();
+            }
+
+            return match KeyBinding::from_key(&key, modifiers) {
+                Some(binding) => {
+                    self.palette.capturing = None;
+                    Task::done(Message::ShortcutBound(action, binding))
+                }
+                None => Task::none(),
+            };
+        }
+
+        if self.palette.open {
+            return self.on_palette_key(state, key, modifiers, text);
+        }
+
+        if self.show_about {
+            return Task::none();
+        }
+
+        match state.settings.shortcuts.action_for(&key, modifiers) {
+            Some(action) => self.on_shortcut(action),
+            None => Task::none(),
+        }
+    }
+
+    /// Occurs when a key is pressed while the command palette is open.
+    fn on_palette_key(
+        &mut self,
+        state: &mut AppState,
+        key: keyboard::Key,
+        modifiers: Modifiers,
+        text: Option<&str>,
+    ) -> Task<Message> {
+        use keyboard::Key;
+        use keyboard::key::Named;
+
+        let shader_names = self.texture_splitter.shader_names();
+
+        match key.as_ref() {
+            Key::Named(Named::Escape) => self.palette.hide(),
+            Key::Named(Named::ArrowDown) => self.palette.move_selection(1, &shader_names),
+            Key::Named(Named::ArrowUp) => self.palette.move_selection(-1, &shader_names),
+            Key::Named(Named::Backspace) => self.palette.backspace(),
+            Key::Named(Named::Enter) => {
+                if let Some(entry) = self.palette.selected_entry(&shader_names) {
+                    return self.on_palette_entry(entry);
+                }
+            }
+            _ if modifiers.command() => {
+                // The palette shortcut toggles it closed again
+                if state.settings.shortcuts.action_for(&key, modifiers)
+                    == Some(ShortcutAction::CommandPalette)
+                {
+                    self.palette.hide();
+                }
+            }
+            _ => {
+                if let Some(text) = text {
+                    self.palette.push_text(text);
+                }
+            }
+        }
+
+        Task::none()
+    }
+
+    /// Occurs when a command palette entry is chosen.
+    fn on_palette_entry(&mut self, entry: PaletteEntry) -> Task<Message> {
+        self.palette.hide();
+
+        match entry {
+            PaletteEntry::Shader(name) => self
+                .texture_splitter
+                .update(TextureSplitterMessage::ShaderSelected(name)),
+            PaletteEntry::Action(action) => self.on_shortcut(action),
+        }
+    }
+
+    /// Occurs when a shortcut action is triggered.
+    fn on_shortcut(&mut self, action: ShortcutAction) -> Task<Message> {
+        let message = match action {
+            ShortcutAction::CommandPalette => {
+                self.palette.show();
+                return Task::none();
+            }
+            ShortcutAction::BrowseFocusedSlot => TextureSplitterMessage::BrowseFocusedSlot,
+            ShortcutAction::SaveAll => TextureSplitterMessage::SaveAllPressed,
+            ShortcutAction::SaveCurrent => TextureSplitterMessage::SaveCurrentPressed,
+            ShortcutAction::NextOutput => TextureSplitterMessage::NextOutput,
+            ShortcutAction::PreviousOutput => TextureSplitterMessage::PreviousOutput,
+            ShortcutAction::ReloadShaders => TextureSplitterMessage::ReloadShaders,
+            ShortcutAction::Clear => TextureSplitterMessage::ClearPressed,
+            ShortcutAction::Undo => TextureSplitterMessage::Undo,
+            ShortcutAction::Redo => TextureSplitterMessage::Redo,
+        };
+
+        self.texture_splitter.update(message)
+    }
+
     /// Occurs when the window has opened.
     fn on_opened(&mut self) -> Task<Message> {
         Task::batch([
@@ -212,22 +380,35 @@ impl MainWindow {
 
     /// Occurs when a file has been dropped onto the window.
     fn on_file_dropped(&mut self, state: &mut AppState, path: PathBuf) -> Task<Message> {
-        if state.is_busy() {
+        // Clear file hovered state once the drop arrives
+        state.file_hovered = false;
+
+        // Only handle drops if not showing about page
+        if state.is_busy() || self.show_about {
             return Task::none();
         }
 
-        // Only handle drops if not showing about page
-        let result = if self.show_about {
-            Task::none()
-        } else {
-            // Texture converter accepts any image file
-            self.texture_splitter.on_file_dropped(path)
-        };
+        state.files_dropped.push(path);
+        self.drop_generation += 1;
+        let generation = self.drop_generation;
 
-        // Clear file hovered state after processing drop
-        state.file_hovered = false;
+        Task::perform(
+            async move {
+                futures_timer::Delay::new(std::time::Duration::from_millis(DROP_SETTLE_MS)).await;
+                generation
+            },
+            |generation| Message::Main(MainMessage::DropsSettled(generation)),
+        )
+    }
 
-        result
+    /// Occurs when no more files have been dropped for a moment.
+    fn on_drops_settled(&mut self, state: &mut AppState, generation: u64) -> Task<Message> {
+        if generation != self.drop_generation {
+            return Task::none();
+        }
+
+        let paths = std::mem::take(&mut state.files_dropped);
+        self.texture_splitter.on_paths_dropped(paths)
     }
 
     /// Shows the main window.
@@ -246,6 +427,16 @@ fn main_background_style(theme: &Theme) -> container::Style {
     }
 }
 
+/// Style for the dimmed backdrop behind the command palette.
+fn palette_backdrop_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(iced::Color::from_rgba(
+            0.0, 0.0, 0.0, 0.5,
+        ))),
+        ..Default::default()
+    }
+}
+
 impl From<TextureSplitterMessage> for MainMessage {
     fn from(message: TextureSplitterMessage) -> Self {
         MainMessage::TextureSplitter(message)